@@ -0,0 +1,111 @@
+use std::{ collections::hash_map::DefaultHasher, hash::{ Hash, Hasher } };
+
+/// Where a sequence of states starts repeating itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub period: usize,
+}
+
+/// A state together with its hash, so that most comparisons only have to look at the hash.
+#[derive(Clone)]
+struct Hashed<T> {
+    hash: u64,
+    state: T,
+}
+
+impl<T: Hash> Hashed<T> {
+    fn new(state: T) -> Self {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+
+        Self { hash: hasher.finish(), state }
+    }
+}
+
+impl<T: Eq> PartialEq for Hashed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.state == other.state
+    }
+}
+
+impl Cycle {
+    /// The index within `0..start + period` of the state that equals state `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Detects the cycle in the sequence `initial, step(initial), step(step(initial)), ...` using
+/// Brent's algorithm.
+///
+/// Only two states are kept in memory at any time. The sequence must eventually repeat, or this
+/// never returns.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::cycle::{ find_cycle, Cycle };
+///
+/// // 0, 1, 2, 3, 4, 2, 3, 4, 2, ...
+/// let cycle = find_cycle(0, |&x| if x < 4 { x + 1 } else { 2 });
+/// assert_eq!(Cycle { start: 2, period: 3 }, cycle);
+/// ```
+pub fn find_cycle<T: Hash + Eq + Clone>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut next = |state: &Hashed<T>| Hashed::new(step(&state.state));
+    let initial = Hashed::new(initial);
+
+    // Find the period by letting the hare run ahead in ever doubling stretches.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = next(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = next(&hare);
+        period += 1;
+    }
+
+    // With the hare exactly one period ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = next(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Computes the state after `n` steps, skipping over every full repetition of the cycle.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::cycle::nth_state;
+///
+/// let step = |&x: &u64| if x < 4 { x + 1 } else { 2 };
+/// assert_eq!(4, nth_state(0, step, 1_000_000_000_000));
+/// assert_eq!(1, nth_state(0, step, 1));
+/// ```
+pub fn nth_state<T: Hash + Eq + Clone>(initial: T, mut step: impl FnMut(&T) -> T, n: usize) -> T {
+    let cycle = find_cycle(initial.clone(), &mut step);
+
+    (0..cycle.reduce(n)).fold(initial, |state, _| step(&state))
+}
@@ -0,0 +1,558 @@
+use std::ops::Range;
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ bytes::complete::tag, character::complete::{ alpha1, line_ending, multispace0, space1, u32 }, multi::{ many1, separated_list1 }, sequence::{ delimited, preceded, terminated, tuple }, Err, error::Error, IResult, Parser };
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<CategoryMap>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoryMap {
+    to: String,
+    map: RangeMap,
+}
+
+/// A total function on `0..i64::MAX` that is linear with slope one on each of its segments.
+///
+/// Values that are not covered by any explicit mapping are mapped onto themselves, just like the
+/// almanac prescribes. Because composing two such functions again yields such a function, the
+/// whole chain from seed to location can be collapsed into a single `RangeMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeMap {
+    /// Sorted, contiguous segments covering the whole domain.
+    segments: Vec<Segment>,
+}
+
+/// The category every almanac starts converting from.
+const FIRST_CATEGORY: &str = "seed";
+
+type RawCategoryMap = (String, String, Vec<(i64, i64, i64)>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    start: i64,
+    end: i64,
+    offset: i64,
+}
+
+#[derive(Debug, Error)]
+pub enum Day05Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Map {index} converts from {found}, but the previous stage produced {expected}")]
+    BrokenChain { index: usize, expected: String, found: String },
+    #[error("Mapping ranges overlap at {0}")]
+    Overlap(i64),
+    #[error("The almanac does not list any seeds")]
+    NoSeeds,
+    #[error("Value {0} is outside of the domain of the maps")]
+    OutOfDomain(i64),
+    #[error("Seed {0} starts a range without a length")]
+    UnpairedSeed(i64),
+}
+
+impl Segment {
+    fn image(&self) -> Range<i64> {
+        (self.start + self.offset)..(self.end + self.offset)
+    }
+}
+
+impl RangeMap {
+    /// The values every map is defined on.
+    pub const DOMAIN: Range<i64> = 0..i64::MAX;
+
+    /// The identity function.
+    pub fn identity() -> Self {
+        Self { segments: vec![Segment { start: Self::DOMAIN.start, end: Self::DOMAIN.end, offset: 0 }] }
+    }
+
+    /// Builds a map from `(destination start, source start, length)` triples, as they are listed
+    /// in the almanac.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day05::RangeMap;
+    ///
+    /// let map = RangeMap::from_entries(&[(50, 98, 2), (52, 50, 48)]).unwrap();
+    /// assert_eq!(Some(81), map.apply(79));
+    /// assert_eq!(Some(14), map.apply(14));
+    /// assert_eq!(Some(50), map.apply(98));
+    /// assert_eq!(None, map.apply(-1));
+    ///
+    /// assert!(RangeMap::from_entries(&[(0, i64::MAX - 7, 100)]).is_err());
+    /// assert!(RangeMap::from_entries(&[(-5, 0, 10)]).is_err());
+    /// ```
+    pub fn from_entries(entries: &[(i64, i64, i64)]) -> Result<Self, Day05Error> {
+        // Both the source and the destination range have to lie within the domain, whose end is
+        // `i64::MAX`, so it is enough for the end of a range not to overflow.
+        let within = |start: i64, len: i64| match start.checked_add(len) {
+            Some(end) if start >= Self::DOMAIN.start => Ok(end),
+            _ => Err(Day05Error::OutOfDomain(start)),
+        };
+
+        let mut explicit: Vec<Segment> = entries.iter()
+            .filter(|(_, _, len)| *len > 0)
+            .map(|&(dest, src, len)| {
+                within(dest, len)?;
+                Ok(Segment { start: src, end: within(src, len)?, offset: dest - src })
+            })
+            .collect::<Result<_, _>>()?;
+        explicit.sort_by_key(|segment| segment.start);
+
+        let mut segments = Vec::with_capacity(explicit.len() * 2 + 1);
+        let mut cursor = 0;
+        for segment in explicit {
+            if segment.start < cursor {
+                return Err(Day05Error::Overlap(segment.start));
+            }
+            if segment.start > cursor {
+                segments.push(Segment { start: cursor, end: segment.start, offset: 0 });
+            }
+            cursor = segment.end;
+            segments.push(segment);
+        }
+        if cursor < Self::DOMAIN.end {
+            segments.push(Segment { start: cursor, end: Self::DOMAIN.end, offset: 0 });
+        }
+
+        Ok(Self::normalized(segments))
+    }
+
+    /// Maps a single value, or returns `None` if it lies outside of [`RangeMap::DOMAIN`].
+    pub fn apply(&self, value: i64) -> Option<i64> {
+        Self::DOMAIN.contains(&value).then(|| value + self.segment_at(value).offset)
+    }
+
+    /// Returns the function that first applies `self` and then `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day05::RangeMap;
+    ///
+    /// let a = RangeMap::from_entries(&[(50, 98, 2), (52, 50, 48)]).unwrap();
+    /// let b = RangeMap::from_entries(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]).unwrap();
+    /// let composed = a.then(&b);
+    ///
+    /// for seed in [0, 14, 49, 50, 79, 97, 98, 99, 100] {
+    ///     assert_eq!(a.apply(seed).and_then(|soil| b.apply(soil)), composed.apply(seed));
+    /// }
+    /// ```
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut segments = Vec::new();
+
+        for segment in &self.segments {
+            let image = segment.image();
+            let first = next.segments.partition_point(|other| other.end <= image.start);
+
+            for other in next.segments[first..].iter().take_while(|other| other.start < image.end) {
+                segments.push(Segment {
+                    start: std::cmp::max(image.start, other.start) - segment.offset,
+                    end: std::cmp::min(image.end, other.end) - segment.offset,
+                    offset: segment.offset + other.offset,
+                });
+            }
+        }
+
+        Self::normalized(segments)
+    }
+
+    /// Maps a whole range of values, returning the (possibly fragmented) image. Values outside of
+    /// [`RangeMap::DOMAIN`] have no image and are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day05::RangeMap;
+    ///
+    /// let map = RangeMap::from_entries(&[(50, 98, 2), (52, 50, 48)]).unwrap();
+    /// assert_eq!(vec![47..50, 52..54], map.apply_range(47..52));
+    /// ```
+    pub fn apply_range(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        self.overlapping(&range)
+            .map(|segment| {
+                (std::cmp::max(range.start, segment.start) + segment.offset)
+                    ..(std::cmp::min(range.end, segment.end) + segment.offset)
+            })
+            .collect()
+    }
+
+    /// Returns every value that maps into `range`, as a sorted list of disjoint ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day05::RangeMap;
+    ///
+    /// let map = RangeMap::from_entries(&[(50, 98, 2), (52, 50, 48)]).unwrap();
+    /// assert_eq!(vec![50..52, 98..100], map.preimage(50..54));
+    /// ```
+    pub fn preimage(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        let mut result: Vec<Range<i64>> = self.segments.iter()
+            .filter_map(|segment| {
+                let image = segment.image();
+                let start = std::cmp::max(image.start, range.start);
+                let end = std::cmp::min(image.end, range.end);
+
+                (start < end).then(|| (start - segment.offset)..(end - segment.offset))
+            })
+            .collect();
+        result.sort_by_key(|range| range.start);
+
+        result.into_iter().fold(Vec::new(), |mut acc, range| {
+            match acc.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => acc.push(range),
+            }
+            acc
+        })
+    }
+
+    /// The smallest value in the image of `range`, if any part of the range lies within
+    /// [`RangeMap::DOMAIN`].
+    pub fn min_in_range(&self, range: Range<i64>) -> Option<i64> {
+        self.overlapping(&range)
+            .map(|segment| std::cmp::max(range.start, segment.start) + segment.offset)
+            .min()
+    }
+
+    fn overlapping<'a>(&'a self, range: &Range<i64>) -> impl Iterator<Item = &'a Segment> + 'a {
+        let first = self.segments.partition_point(|segment| segment.end <= range.start);
+        let end = range.end;
+
+        self.segments[first..].iter().take_while(move |segment| segment.start < end)
+    }
+
+    fn segment_at(&self, value: i64) -> &Segment {
+        &self.segments[self.segments.partition_point(|segment| segment.end <= value)]
+    }
+
+    /// Merges neighbouring segments that share the same offset.
+    fn normalized(segments: Vec<Segment>) -> Self {
+        let segments = segments.into_iter().fold(Vec::<Segment>::new(), |mut acc, segment| {
+            match acc.last_mut() {
+                Some(last) if last.offset == segment.offset && last.end == segment.start => last.end = segment.end,
+                _ => acc.push(segment),
+            }
+            acc
+        });
+
+        Self { segments }
+    }
+}
+
+impl Default for RangeMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Almanac {
+    /// Collapses every stage of the almanac into a single seed-to-location map.
+    pub fn composed(&self) -> RangeMap {
+        self.maps.iter().fold(RangeMap::identity(), |acc, stage| acc.then(&stage.map))
+    }
+
+    /// The seed list interpreted as `(start, length)` pairs, like part 2 does.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<i64>>, Day05Error> {
+        let pairs = self.seeds.chunks_exact(2);
+        if let [unpaired] = pairs.remainder() {
+            return Err(Day05Error::UnpairedSeed(*unpaired));
+        }
+
+        Ok(pairs.map(|pair| pair[0]..(pair[0] + pair[1])).collect())
+    }
+
+    /// Returns every seed that ends up at one of the given locations, as a sorted list of disjoint
+    /// ranges. This is not limited to the seeds the almanac lists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day05::parse;
+    ///
+    /// let almanac = parse(EXAMPLE).unwrap();
+    /// assert_eq!(vec![82..83], almanac.seeds_for_locations(46..47));
+    /// # const EXAMPLE: &str = "seeds: 79 14 55 13
+    /// #
+    /// # seed-to-soil map:
+    /// # 50 98 2
+    /// # 52 50 48
+    /// #
+    /// # soil-to-fertilizer map:
+    /// # 0 15 37
+    /// # 37 52 2
+    /// # 39 0 15
+    /// #
+    /// # fertilizer-to-water map:
+    /// # 49 53 8
+    /// # 0 11 42
+    /// # 42 0 7
+    /// # 57 7 4
+    /// #
+    /// # water-to-light map:
+    /// # 88 18 7
+    /// # 18 25 70
+    /// #
+    /// # light-to-temperature map:
+    /// # 45 77 23
+    /// # 81 45 19
+    /// # 68 64 13
+    /// #
+    /// # temperature-to-humidity map:
+    /// # 0 69 1
+    /// # 1 0 69
+    /// #
+    /// # humidity-to-location map:
+    /// # 60 56 37
+    /// # 56 93 4";
+    /// ```
+    pub fn seeds_for_locations(&self, locations: Range<i64>) -> Vec<Range<i64>> {
+        self.composed().preimage(locations)
+    }
+
+    /// Follows a single seed through every stage, listing the category and value it has after
+    /// each conversion. The first entry is the seed itself, the last one its location. Returns
+    /// `None` for seeds outside of [`RangeMap::DOMAIN`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day05::parse;
+    ///
+    /// let almanac = parse(EXAMPLE).unwrap();
+    /// assert_eq!(vec![
+    ///     ("seed", 79),
+    ///     ("soil", 81),
+    ///     ("fertilizer", 81),
+    ///     ("water", 81),
+    ///     ("light", 74),
+    ///     ("temperature", 78),
+    ///     ("humidity", 78),
+    ///     ("location", 82),
+    /// ], almanac.trace(79).unwrap());
+    /// assert_eq!(None, almanac.trace(-1));
+    /// # const EXAMPLE: &str = "seeds: 79 14 55 13
+    /// #
+    /// # seed-to-soil map:
+    /// # 50 98 2
+    /// # 52 50 48
+    /// #
+    /// # soil-to-fertilizer map:
+    /// # 0 15 37
+    /// # 37 52 2
+    /// # 39 0 15
+    /// #
+    /// # fertilizer-to-water map:
+    /// # 49 53 8
+    /// # 0 11 42
+    /// # 42 0 7
+    /// # 57 7 4
+    /// #
+    /// # water-to-light map:
+    /// # 88 18 7
+    /// # 18 25 70
+    /// #
+    /// # light-to-temperature map:
+    /// # 45 77 23
+    /// # 81 45 19
+    /// # 68 64 13
+    /// #
+    /// # temperature-to-humidity map:
+    /// # 0 69 1
+    /// # 1 0 69
+    /// #
+    /// # humidity-to-location map:
+    /// # 60 56 37
+    /// # 56 93 4";
+    /// ```
+    pub fn trace(&self, seed: i64) -> Option<Vec<(&str, i64)>> {
+        if !RangeMap::DOMAIN.contains(&seed) {
+            return None;
+        }
+
+        let mut trace = Vec::with_capacity(self.maps.len() + 1);
+        trace.push((FIRST_CATEGORY, seed));
+
+        self.maps.iter().try_fold(seed, |value, stage| {
+            let value = stage.map.apply(value)?;
+            trace.push((stage.to.as_str(), value));
+            Some(value)
+        })?;
+
+        Some(trace)
+    }
+
+    fn parser(input: &str) -> IResult<&str, (Vec<i64>, Vec<RawCategoryMap>)> {
+        tuple((
+            preceded(tag("seeds:"), many1(preceded(space1, u32.map(i64::from)))),
+            many1(preceded(many1(line_ending), CategoryMap::parser)),
+        ))(input)
+    }
+}
+
+impl CategoryMap {
+    fn parser(input: &str) -> IResult<&str, RawCategoryMap> {
+        tuple((
+            alpha1,
+            delimited(tag("-to-"), alpha1, tuple((tag(" map:"), line_ending))),
+            separated_list1(line_ending, tuple((
+                terminated(u32.map(i64::from), space1),
+                terminated(u32.map(i64::from), space1),
+                u32.map(i64::from),
+            ))),
+        ))
+            .map(|(from, to, entries): (&str, &str, _)| (from.to_owned(), to.to_owned(), entries))
+            .parse(input)
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day05::parse;
+/// assert!(parse("seeds: 79 14
+///
+/// seed-to-soil map:
+/// 50 98 2
+///
+/// fertilizer-to-water map:
+/// 49 53 8").is_err());
+///
+/// assert!(parse("seeds: 9223372036854775807
+///
+/// seed-to-soil map:
+/// 50 98 2").is_err());
+/// assert!(parse("seeds: -1
+///
+/// seed-to-soil map:
+/// 50 98 2").is_err());
+/// ```
+#[aoc_generator(day5)]
+pub fn parse(input: &str) -> Result<Almanac, Day05Error> {
+    let (seeds, raw_maps) = match terminated(Almanac::parser, multispace0)(input) {
+        Ok(("", result)) => result,
+        Ok((rest, _)) => return Err(Day05Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day05Error::ParseError(err.to_owned())),
+    };
+
+    let mut maps: Vec<CategoryMap> = Vec::with_capacity(raw_maps.len());
+    for (index, (from, to, entries)) in raw_maps.into_iter().enumerate() {
+        let expected = maps.last().map(|previous| previous.to.as_str()).unwrap_or(FIRST_CATEGORY);
+        if from != expected {
+            return Err(Day05Error::BrokenChain { index, expected: expected.to_owned(), found: from });
+        }
+
+        maps.push(CategoryMap { to, map: RangeMap::from_entries(&entries)? });
+    }
+
+    Ok(Almanac { seeds, maps })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day05::{ parse, part1 };
+/// assert_eq!(35, part1(&parse(EXAMPLE).unwrap()).unwrap());
+/// # const EXAMPLE: &str = "seeds: 79 14 55 13
+/// #
+/// # seed-to-soil map:
+/// # 50 98 2
+/// # 52 50 48
+/// #
+/// # soil-to-fertilizer map:
+/// # 0 15 37
+/// # 37 52 2
+/// # 39 0 15
+/// #
+/// # fertilizer-to-water map:
+/// # 49 53 8
+/// # 0 11 42
+/// # 42 0 7
+/// # 57 7 4
+/// #
+/// # water-to-light map:
+/// # 88 18 7
+/// # 18 25 70
+/// #
+/// # light-to-temperature map:
+/// # 45 77 23
+/// # 81 45 19
+/// # 68 64 13
+/// #
+/// # temperature-to-humidity map:
+/// # 0 69 1
+/// # 1 0 69
+/// #
+/// # humidity-to-location map:
+/// # 60 56 37
+/// # 56 93 4";
+/// ```
+#[aoc(day5, part1)]
+pub fn part1(almanac: &Almanac) -> Result<i64, Day05Error> {
+    let map = almanac.composed();
+
+    let locations = almanac.seeds.iter()
+        .map(|&seed| map.apply(seed).ok_or(Day05Error::OutOfDomain(seed)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    locations.into_iter().min().ok_or(Day05Error::NoSeeds)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day05::{ parse, part2, Day05Error };
+/// assert_eq!(46, part2(&parse(EXAMPLE).unwrap()).unwrap());
+/// assert!(matches!(part2(&parse("seeds: 1 2 3\n\nseed-to-soil map:\n50 98 2").unwrap()), Err(Day05Error::UnpairedSeed(3))));
+/// # const EXAMPLE: &str = "seeds: 79 14 55 13
+/// #
+/// # seed-to-soil map:
+/// # 50 98 2
+/// # 52 50 48
+/// #
+/// # soil-to-fertilizer map:
+/// # 0 15 37
+/// # 37 52 2
+/// # 39 0 15
+/// #
+/// # fertilizer-to-water map:
+/// # 49 53 8
+/// # 0 11 42
+/// # 42 0 7
+/// # 57 7 4
+/// #
+/// # water-to-light map:
+/// # 88 18 7
+/// # 18 25 70
+/// #
+/// # light-to-temperature map:
+/// # 45 77 23
+/// # 81 45 19
+/// # 68 64 13
+/// #
+/// # temperature-to-humidity map:
+/// # 0 69 1
+/// # 1 0 69
+/// #
+/// # humidity-to-location map:
+/// # 60 56 37
+/// # 56 93 4";
+/// ```
+#[aoc(day5, part2)]
+pub fn part2(almanac: &Almanac) -> Result<i64, Day05Error> {
+    let map = almanac.composed();
+
+    almanac.seed_ranges()?
+        .into_iter()
+        .filter_map(|range| map.min_in_range(range))
+        .min()
+        .ok_or(Day05Error::NoSeeds)
+}
@@ -0,0 +1,152 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ bytes::complete::tag, character::complete::{ line_ending, multispace0, space0, space1, u128 }, multi::separated_list1, sequence::{ delimited, preceded, separated_pair }, Err, error::Error, IResult };
+use num::BigUint;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u128,
+    pub distance: u128,
+}
+
+#[derive(Debug, Error)]
+pub enum Day06Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Found {times} times but {distances} distances")]
+    LengthMismatch { times: usize, distances: usize },
+}
+
+impl Race {
+    pub fn new(time: u128, distance: u128) -> Self {
+        Self { time, distance }
+    }
+
+    /// Counts the hold times that beat the record of this race.
+    ///
+    /// Holding the button for `h` milliseconds travels `h * (time - h)` millimeters, so the
+    /// winning hold times lie strictly between the roots of `h² - time * h + distance`. Those are
+    /// located with an exact integer square root of the discriminant, which is computed with
+    /// arbitrary precision so that even `u128` races can not overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day06::Race;
+    ///
+    /// assert_eq!(4, Race::new(7, 9).ways_to_win());
+    /// assert_eq!(9, Race::new(30, 200).ways_to_win());
+    /// assert_eq!(0, Race::new(4, 4).ways_to_win());
+    /// assert_eq!(u128::MAX - 1, Race::new(u128::MAX, 0).ways_to_win());
+    /// ```
+    pub fn ways_to_win(&self) -> u128 {
+        let time = BigUint::from(self.time);
+        let square = &time * &time;
+        let four_distance = BigUint::from(self.distance) * 4u32;
+
+        if square <= four_distance {
+            return 0;
+        }
+
+        let root = (square - four_distance).sqrt();
+        let root = u128::try_from(root).expect("the root never exceeds the race time");
+
+        // The estimate is off by at most one in either direction due to the flooring.
+        let mut shortest = (self.time - root) / 2;
+        while shortest > 0 && self.beats_record(shortest - 1) {
+            shortest -= 1;
+        }
+        while shortest <= self.time / 2 && !self.beats_record(shortest) {
+            shortest += 1;
+        }
+
+        if shortest > self.time / 2 {
+            0
+        } else {
+            // Winning hold times are symmetric around `time / 2`.
+            self.time - 2 * shortest + 1
+        }
+    }
+
+    fn beats_record(&self, hold: u128) -> bool {
+        BigUint::from(hold) * (self.time - hold) > BigUint::from(self.distance)
+    }
+}
+
+fn numbers<'a>(label: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<u128>> {
+    preceded(tag(label), preceded(space0, separated_list1(space1, u128)))
+}
+
+fn races(input: &str) -> IResult<&str, (Vec<u128>, Vec<u128>)> {
+    delimited(
+        multispace0,
+        separated_pair(numbers("Time:"), line_ending, numbers("Distance:")),
+        multispace0,
+    )(input)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day06::{ parse, Race };
+/// assert_eq!(vec![Race::new(7, 9), Race::new(15, 40), Race::new(30, 200)], parse("Time:      7  15   30
+/// Distance:  9  40  200").unwrap());
+/// ```
+#[aoc_generator(day6, part1)]
+pub fn parse(input: &str) -> Result<Vec<Race>, Day06Error> {
+    let (times, distances) = match races(input) {
+        Ok(("", result)) => result,
+        Ok((rest, _)) => return Err(Day06Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day06Error::ParseError(err.to_owned())),
+    };
+
+    if times.len() != distances.len() {
+        return Err(Day06Error::LengthMismatch { times: times.len(), distances: distances.len() });
+    }
+
+    Ok(times.into_iter().zip(distances).map(|(time, distance)| Race { time, distance }).collect())
+}
+
+/// Parses the input as a single race, ignoring the bad kerning between the digits.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day06::{ parse_kerning, Race };
+/// assert_eq!(Race::new(71530, 940200), parse_kerning("Time:      7  15   30
+/// Distance:  9  40  200").unwrap());
+/// ```
+#[aoc_generator(day6, part2)]
+pub fn parse_kerning(input: &str) -> Result<Race, Day06Error> {
+    let joined: String = input.chars().filter(|chr| *chr != ' ' && *chr != '\t').collect();
+    let mut races = parse(&joined)?;
+
+    // With the whitespace gone every line holds exactly one number, so there is exactly one race.
+    Ok(races.remove(0))
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day06::{ parse, part1 };
+/// assert_eq!(288, part1(&parse("Time:      7  15   30
+/// Distance:  9  40  200").unwrap()));
+/// ```
+#[aoc(day6, part1)]
+pub fn part1(races: &[Race]) -> u128 {
+    races.iter().map(Race::ways_to_win).product()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day06::{ parse_kerning, part2 };
+/// assert_eq!(71503, part2(&parse_kerning("Time:      7  15   30
+/// Distance:  9  40  200").unwrap()));
+/// ```
+#[aoc(day6, part2)]
+pub fn part2(race: &Race) -> u128 {
+    race.ways_to_win()
+}
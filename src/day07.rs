@@ -0,0 +1,244 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ character::complete::{ alphanumeric1, line_ending, multispace0, space1, u64 }, multi::separated_list0, sequence::{ separated_pair, terminated }, Err, error::Error, IResult, Parser };
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    cards: Vec<u8>,
+    bid: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+/// How strong a hand is under a particular rule set.
+///
+/// Hands are ordered by category first and by the strength of their cards, from left to right,
+/// second.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Strength {
+    pub category: Category,
+    pub cards: Vec<u8>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day07Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Card {0} is not part of the deck")]
+    UnknownCard(char),
+}
+
+/// Decides how strong each card is and which cards act as wildcards.
+pub trait Rules {
+    /// The strength of a card, or `None` if the card is not part of the deck.
+    fn card_strength(&self, card: u8) -> Option<u8>;
+
+    /// Whether the card takes on whatever value makes the hand strongest.
+    fn is_wildcard(&self, _card: u8) -> bool {
+        false
+    }
+}
+
+/// The rules of part 1.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Standard;
+
+/// The rules of part 2: `J` is the weakest card, but acts as a wildcard.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Jokers;
+
+/// Rules with an arbitrary deck and an arbitrary set of wildcards.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day07::{ parse, total_winnings, CustomRules };
+///
+/// let hands = parse("32T3K 765
+/// T55J5 684
+/// KK677 28
+/// KTJJT 220
+/// QQQJA 483").unwrap();
+///
+/// // The deck of part 1, but with sevens wild.
+/// let rules = CustomRules::new("23456789TJQKA", "7");
+/// assert_eq!(5329, total_winnings(&hands, &rules).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomRules {
+    alphabet: Vec<u8>,
+    wildcards: Vec<u8>,
+}
+
+const STANDARD_DECK: &[u8] = b"23456789TJQKA";
+const JOKER_DECK: &[u8] = b"J23456789TQKA";
+
+impl Rules for Standard {
+    fn card_strength(&self, card: u8) -> Option<u8> {
+        position(STANDARD_DECK, card)
+    }
+}
+
+impl Rules for Jokers {
+    fn card_strength(&self, card: u8) -> Option<u8> {
+        position(JOKER_DECK, card)
+    }
+
+    fn is_wildcard(&self, card: u8) -> bool {
+        card == b'J'
+    }
+}
+
+impl CustomRules {
+    /// Creates a rule set from the cards of the deck, ordered from weak to strong, and the cards
+    /// that are wild.
+    pub fn new(alphabet: &str, wildcards: &str) -> Self {
+        Self {
+            alphabet: alphabet.bytes().collect(),
+            wildcards: wildcards.bytes().collect(),
+        }
+    }
+}
+
+impl Rules for CustomRules {
+    fn card_strength(&self, card: u8) -> Option<u8> {
+        position(&self.alphabet, card)
+    }
+
+    fn is_wildcard(&self, card: u8) -> bool {
+        self.wildcards.contains(&card)
+    }
+}
+
+fn position(deck: &[u8], card: u8) -> Option<u8> {
+    deck.iter().position(|&c| c == card).map(|idx| idx as u8)
+}
+
+impl Hand {
+    pub fn new(cards: &str, bid: u64) -> Self {
+        Self { cards: cards.bytes().collect(), bid }
+    }
+
+    pub fn bid(&self) -> u64 {
+        self.bid
+    }
+
+    /// Classifies the hand, letting every wildcard join the largest group of equal cards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day07::{ Category, Hand, Jokers, Standard };
+    ///
+    /// assert_eq!(Category::TwoPair, Hand::new("KTJJT", 220).category(&Standard));
+    /// assert_eq!(Category::FourOfAKind, Hand::new("KTJJT", 220).category(&Jokers));
+    /// assert_eq!(Category::FiveOfAKind, Hand::new("JJJJJ", 1).category(&Jokers));
+    /// ```
+    pub fn category(&self, rules: &impl Rules) -> Category {
+        let mut counts: Vec<(u8, usize)> = Vec::with_capacity(self.cards.len());
+        let mut wildcards = 0;
+
+        for &card in &self.cards {
+            if rules.is_wildcard(card) {
+                wildcards += 1;
+            } else if let Some(entry) = counts.iter_mut().find(|(c, _)| *c == card) {
+                entry.1 += 1;
+            } else {
+                counts.push((card, 1));
+            }
+        }
+
+        let mut counts: Vec<usize> = counts.into_iter().map(|(_, count)| count).collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        match counts.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None => counts.push(wildcards),
+        }
+
+        match (counts[0], counts.get(1).copied().unwrap_or(0)) {
+            (5.., _) => Category::FiveOfAKind,
+            (4, _) => Category::FourOfAKind,
+            (3, 2..) => Category::FullHouse,
+            (3, _) => Category::ThreeOfAKind,
+            (2, 2..) => Category::TwoPair,
+            (2, _) => Category::OnePair,
+            _ => Category::HighCard,
+        }
+    }
+
+    /// Computes the strength of the hand, failing if it contains cards outside of the deck.
+    pub fn strength(&self, rules: &impl Rules) -> Result<Strength, Day07Error> {
+        let cards = self.cards.iter()
+            .map(|&card| rules.card_strength(card).ok_or(Day07Error::UnknownCard(card as char)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Strength { category: self.category(rules), cards })
+    }
+
+    fn parser(input: &str) -> IResult<&str, Self> {
+        separated_pair(alphanumeric1, space1, u64)
+            .map(|(cards, bid)| Hand::new(cards, bid))
+            .parse(input)
+    }
+}
+
+/// Ranks the hands under the given rules and sums every bid multiplied by its rank.
+pub fn total_winnings(hands: &[Hand], rules: &impl Rules) -> Result<u64, Day07Error> {
+    let mut ranked = hands.iter()
+        .map(|hand| Ok((hand.strength(rules)?, hand.bid)))
+        .collect::<Result<Vec<_>, Day07Error>>()?;
+    ranked.sort_unstable();
+
+    Ok(ranked.iter().zip(1..).map(|((_, bid), rank)| bid * rank).sum())
+}
+
+#[aoc_generator(day7)]
+pub fn parse(input: &str) -> Result<Vec<Hand>, Day07Error> {
+    match terminated(separated_list0(line_ending, Hand::parser), multispace0)(input) {
+        Ok(("", hands)) => Ok(hands),
+        Ok((rest, _)) => Err(Day07Error::Rest(rest.to_owned())),
+        Err(err) => Err(Day07Error::ParseError(err.to_owned())),
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day07::{ parse, part1 };
+/// assert_eq!(6440, part1(&parse("32T3K 765
+/// T55J5 684
+/// KK677 28
+/// KTJJT 220
+/// QQQJA 483").unwrap()).unwrap());
+/// ```
+#[aoc(day7, part1)]
+pub fn part1(hands: &[Hand]) -> Result<u64, Day07Error> {
+    total_winnings(hands, &Standard)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day07::{ parse, part2 };
+/// assert_eq!(5905, part2(&parse("32T3K 765
+/// T55J5 684
+/// KK677 28
+/// KTJJT 220
+/// QQQJA 483").unwrap()).unwrap());
+/// ```
+#[aoc(day7, part2)]
+pub fn part2(hands: &[Hand]) -> Result<u64, Day07Error> {
+    total_winnings(hands, &Jokers)
+}
@@ -0,0 +1,150 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ character::complete::{ i64, line_ending, multispace0, space1 }, multi::{ separated_list0, separated_list1 }, sequence::terminated, Err, error::Error, IResult };
+use num::{ BigInt, One, Zero };
+use thiserror::Error;
+
+/// The polynomial through a history of readings, in Newton's forward-difference form.
+///
+/// All arithmetic is done on arbitrary precision integers, so neither long histories nor
+/// extrapolating far away from the readings can overflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    /// The first element of every row of differences, up to the last non-zero one.
+    differences: Vec<BigInt>,
+    len: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum Day09Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+}
+
+impl Sequence {
+    pub fn new(history: &[i64]) -> Self {
+        let mut row: Vec<BigInt> = history.iter().copied().map(BigInt::from).collect();
+        let mut differences = Vec::with_capacity(row.len());
+
+        while !row.is_empty() && !row.iter().all(Zero::is_zero) {
+            differences.push(row[0].clone());
+            row = row.windows(2).map(|pair| &pair[1] - &pair[0]).collect();
+        }
+
+        Self { differences, len: history.len() }
+    }
+
+    /// The degree of the polynomial, or `None` if every reading is zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    /// Evaluates the polynomial at `index`, where index 0 is the first reading. Negative indices
+    /// lie before the history.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day09::Sequence;
+    /// use num::BigInt;
+    ///
+    /// let squares = Sequence::new(&[0, 1, 4, 9]);
+    /// assert_eq!(Some(2), squares.degree());
+    /// assert_eq!(BigInt::from(3_000_000_000_000_000i64).pow(2), squares.at(&BigInt::from(3_000_000_000_000_000i64)));
+    /// assert_eq!(BigInt::from(25), squares.at(&BigInt::from(-5)));
+    /// assert_eq!(BigInt::from(i64::MAX).pow(4), squares.at(&BigInt::from(i64::MAX).pow(2)));
+    /// ```
+    pub fn at(&self, index: &BigInt) -> BigInt {
+        let mut binomial = BigInt::one();
+        let mut result = BigInt::zero();
+
+        // Sums every difference times `index` choose `k`, which is an integer for any integer
+        // `index`, so each division below is exact.
+        for (k, difference) in self.differences.iter().enumerate() {
+            result += difference * &binomial;
+            binomial = binomial * (index - k) / (k + 1);
+        }
+
+        result
+    }
+
+    /// Extrapolates the value `steps` readings after the last one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day09::Sequence;
+    /// use num::BigInt;
+    ///
+    /// let sequence = Sequence::new(&[10, 13, 16, 21, 30, 45]);
+    /// assert_eq!(BigInt::from(68), sequence.next(1));
+    /// assert_eq!(BigInt::from(101), sequence.next(2));
+    ///
+    /// let squares = Sequence::new(&[0, 1, 4, 9]);
+    /// assert_eq!((BigInt::from(i64::MAX) + 3u8).pow(2), squares.next(i64::MAX));
+    /// ```
+    pub fn next(&self, steps: i64) -> BigInt {
+        self.at(&(BigInt::from(self.len) - 1 + steps))
+    }
+
+    /// Extrapolates the value `steps` readings before the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day09::Sequence;
+    /// use num::BigInt;
+    ///
+    /// let sequence = Sequence::new(&[10, 13, 16, 21, 30, 45]);
+    /// assert_eq!(BigInt::from(5), sequence.previous(1));
+    /// assert_eq!(BigInt::from(-19), sequence.previous(3));
+    ///
+    /// let squares = Sequence::new(&[0, 1, 4, 9]);
+    /// assert_eq!(BigInt::from(i64::MIN).pow(2), squares.previous(i64::MIN));
+    /// ```
+    pub fn previous(&self, steps: i64) -> BigInt {
+        self.at(&-BigInt::from(steps))
+    }
+}
+
+fn histories(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
+    terminated(separated_list0(line_ending, separated_list1(space1, i64)), multispace0)(input)
+}
+
+#[aoc_generator(day9)]
+pub fn parse(input: &str) -> Result<Vec<Sequence>, Day09Error> {
+    match histories(input) {
+        Ok(("", histories)) => Ok(histories.iter().map(|history| Sequence::new(history)).collect()),
+        Ok((rest, _)) => Err(Day09Error::Rest(rest.to_owned())),
+        Err(err) => Err(Day09Error::ParseError(err.to_owned())),
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day09::{ parse, part1 };
+/// use num::BigInt;
+/// assert_eq!(BigInt::from(114), part1(&parse("0 3 6 9 12 15
+/// 1 3 6 10 15 21
+/// 10 13 16 21 30 45").unwrap()));
+/// ```
+#[aoc(day9, part1)]
+pub fn part1(sequences: &[Sequence]) -> BigInt {
+    sequences.iter().map(|sequence| sequence.next(1)).sum()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day09::{ parse, part2 };
+/// use num::BigInt;
+/// assert_eq!(BigInt::from(2), part2(&parse("0 3 6 9 12 15
+/// 1 3 6 10 15 21
+/// 10 13 16 21 30 45").unwrap()));
+/// ```
+#[aoc(day9, part2)]
+pub fn part2(sequences: &[Sequence]) -> BigInt {
+    sequences.iter().map(|sequence| sequence.previous(1)).sum()
+}
@@ -0,0 +1,304 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+type Coordinate = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// A tile of the maze, stored as the set of directions it connects to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Tile(u8);
+
+#[derive(Debug, Clone)]
+pub struct Maze {
+    tiles: Vec<Vec<Tile>>,
+    start: Coordinate,
+}
+
+/// The loop that runs through the start tile.
+#[derive(Debug, Clone)]
+pub struct MainLoop {
+    /// The pipe that is hidden under the start tile.
+    pub start_tile: char,
+    /// Every tile of the loop in walking order, beginning at the start tile.
+    pub tiles: Vec<Coordinate>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day10Error {
+    #[error("Unknown tile {0:?} at {1:?}")]
+    UnknownTile(char, Coordinate),
+    #[error("The maze does not contain a start tile")]
+    MissingStart,
+    #[error("The maze contains a second start tile at {0:?}")]
+    MultipleStarts(Coordinate),
+    #[error("The start tile connects to {0} pipes instead of 2")]
+    StartConnections(usize),
+    #[error("The loop breaks off at {0:?}")]
+    OpenLoop(Coordinate),
+}
+
+const DIRECTIONS: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+impl Direction {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+impl Tile {
+    const GROUND: Tile = Tile(0);
+    const START: Tile = Tile(0xF0);
+
+    fn connects(self, direction: Direction) -> bool {
+        self.0 & direction.bit() != 0
+    }
+
+    fn from_directions(a: Direction, b: Direction) -> Self {
+        Tile(a.bit() | b.bit())
+    }
+
+    /// The only direction out of this pipe other than `direction`.
+    fn other_exit(self, direction: Direction) -> Option<Direction> {
+        DIRECTIONS.into_iter().find(|&other| other != direction && self.connects(other))
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        use Direction::*;
+
+        match value {
+            '|' => Ok(Tile::from_directions(North, South)),
+            '-' => Ok(Tile::from_directions(East, West)),
+            'L' => Ok(Tile::from_directions(North, East)),
+            'J' => Ok(Tile::from_directions(North, West)),
+            '7' => Ok(Tile::from_directions(South, West)),
+            'F' => Ok(Tile::from_directions(South, East)),
+            '.' => Ok(Tile::GROUND),
+            'S' => Ok(Tile::START),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<Tile> for char {
+    fn from(value: Tile) -> Self {
+        ['.', '?', '?', 'L', '?', '|', 'F', '?', '?', 'J', '-', '?', '7', '?', '?', '?']
+            .get(value.0 as usize)
+            .copied()
+            .unwrap_or('S')
+    }
+}
+
+impl Maze {
+    fn tile(&self, (x, y): Coordinate) -> Tile {
+        self.tiles.get(y).and_then(|row| row.get(x)).copied().unwrap_or(Tile::GROUND)
+    }
+
+    fn step(&self, (x, y): Coordinate, direction: Direction) -> Option<Coordinate> {
+        let next = match direction {
+            Direction::North => (x, y.checked_sub(1)?),
+            Direction::East => (x + 1, y),
+            Direction::South => (x, y + 1),
+            Direction::West => (x.checked_sub(1)?, y),
+        };
+
+        (next.1 < self.tiles.len() && next.0 < self.tiles[next.1].len()).then_some(next)
+    }
+
+    /// Works out which pipe hides under the start tile, based on the pipes that connect to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day10::parse;
+    ///
+    /// assert_eq!('F', parse(".....
+    /// .S-7.
+    /// .|.|.
+    /// .L-J.
+    /// .....").unwrap().start_tile().unwrap());
+    ///
+    /// assert!(parse(".|...
+    /// -S-7.
+    /// .|.|.
+    /// .L-J.
+    /// .....").unwrap().start_tile().is_err());
+    /// ```
+    pub fn start_tile(&self) -> Result<char, Day10Error> {
+        self.start_exits().map(|(a, b)| Tile::from_directions(a, b).into())
+    }
+
+    fn start_exits(&self) -> Result<(Direction, Direction), Day10Error> {
+        let exits: Vec<Direction> = DIRECTIONS.into_iter()
+            .filter(|&direction| {
+                self.step(self.start, direction)
+                    .is_some_and(|neighbour| self.tile(neighbour).connects(direction.opposite()))
+            })
+            .collect();
+
+        match exits[..] {
+            [a, b] => Ok((a, b)),
+            _ => Err(Day10Error::StartConnections(exits.len())),
+        }
+    }
+
+    /// Walks the loop that runs through the start tile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day10::{ parse, Day10Error };
+    ///
+    /// let main_loop = parse("..F7.
+    /// .FJ|.
+    /// SJ.L7
+    /// |F--J
+    /// LJ...").unwrap().main_loop().unwrap();
+    /// assert_eq!('F', main_loop.start_tile);
+    /// assert_eq!(16, main_loop.tiles.len());
+    ///
+    /// assert!(matches!(parse("S-7
+    /// |.|
+    /// L-.").unwrap().main_loop(), Err(Day10Error::OpenLoop((2, 2)))));
+    /// ```
+    pub fn main_loop(&self) -> Result<MainLoop, Day10Error> {
+        let (exit, _) = self.start_exits()?;
+        let mut tiles = vec![self.start];
+        let mut position = self.start;
+        let mut heading = exit;
+
+        loop {
+            position = self.step(position, heading).ok_or(Day10Error::OpenLoop(position))?;
+            if position == self.start {
+                break;
+            }
+
+            let came_from = heading.opposite();
+            let tile = self.tile(position);
+            if !tile.connects(came_from) {
+                return Err(Day10Error::OpenLoop(position));
+            }
+
+            tiles.push(position);
+            heading = tile.other_exit(came_from).ok_or(Day10Error::OpenLoop(position))?;
+        }
+
+        Ok(MainLoop { start_tile: self.start_tile()?, tiles })
+    }
+}
+
+impl MainLoop {
+    /// The number of tiles enclosed by the loop.
+    ///
+    /// The shoelace formula gives the area of the polygon through the centres of the loop tiles,
+    /// and Pick's theorem turns that into the number of tile centres strictly inside of it.
+    pub fn enclosed(&self) -> usize {
+        let twice_area = self.tiles.iter()
+            .zip(self.tiles.iter().cycle().skip(1))
+            .map(|(&(x1, y1), &(x2, y2))| (x1 * y2) as isize - (x2 * y1) as isize)
+            .sum::<isize>()
+            .unsigned_abs();
+
+        (twice_area + 2 - self.tiles.len()) / 2
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day10::{ parse, Day10Error };
+///
+/// assert!(matches!(parse("..\n.."), Err(Day10Error::MissingStart)));
+/// assert!(matches!(parse("S.\n.S"), Err(Day10Error::MultipleStarts((1, 1)))));
+/// assert!(matches!(parse("S.\n.x"), Err(Day10Error::UnknownTile('x', (1, 1)))));
+/// ```
+#[aoc_generator(day10)]
+pub fn parse(input: &str) -> Result<Maze, Day10Error> {
+    let mut start = None;
+
+    let tiles = input.lines().enumerate().map(|(y, line)| {
+        line.chars().enumerate().map(|(x, chr)| {
+            let tile = Tile::try_from(chr).map_err(|chr| Day10Error::UnknownTile(chr, (x, y)))?;
+
+            if tile == Tile::START && start.replace((x, y)).is_some() {
+                return Err(Day10Error::MultipleStarts((x, y)));
+            }
+
+            Ok(tile)
+        }).collect()
+    }).collect::<Result<_, _>>()?;
+
+    Ok(Maze { tiles, start: start.ok_or(Day10Error::MissingStart)? })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day10::{ parse, part1 };
+///
+/// assert_eq!(4, part1(&parse(".....
+/// .S-7.
+/// .|.|.
+/// .L-J.
+/// .....").unwrap()).unwrap());
+///
+/// assert_eq!(8, part1(&parse("..F7.
+/// .FJ|.
+/// SJ.L7
+/// |F--J
+/// LJ...").unwrap()).unwrap());
+/// ```
+#[aoc(day10, part1)]
+pub fn part1(maze: &Maze) -> Result<usize, Day10Error> {
+    Ok(maze.main_loop()?.tiles.len() / 2)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day10::{ parse, part2 };
+///
+/// assert_eq!(4, part2(&parse("..........
+/// .S------7.
+/// .|F----7|.
+/// .||....||.
+/// .||....||.
+/// .|L-7F-J|.
+/// .|..||..|.
+/// .L--JL--J.
+/// ..........").unwrap()).unwrap());
+///
+/// assert_eq!(10, part2(&parse("FF7FSF7F7F7F7F7F---7
+/// L|LJ||||||||||||F--J
+/// FL-7LJLJ||||||LJL-77
+/// F--JF--7||LJLJ7F7FJ-
+/// L---JF-JLJ.||-FJLJJ7
+/// |F|F-JF---7F7-L7L|7|
+/// |FFJF7L7F-JF7|JL---7
+/// 7-L-JL7||F7|L7F-7F7|
+/// L.L7LFJ|||||FJL7||LJ
+/// L7JLJL-JLJLJL--JLJ.L").unwrap()).unwrap());
+/// ```
+#[aoc(day10, part2)]
+pub fn part2(maze: &Maze) -> Result<usize, Day10Error> {
+    Ok(maze.main_loop()?.enclosed())
+}
@@ -0,0 +1,105 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+type Coordinate = (u64, u64);
+
+#[derive(Debug, Error)]
+pub enum Day11Error {
+    #[error("Unknown tile {0:?} at {1:?}")]
+    UnknownTile(char, Coordinate),
+}
+
+#[aoc_generator(day11)]
+pub fn parse(input: &str) -> Result<Vec<Coordinate>, Day11Error> {
+    input.lines().zip(0..).flat_map(|(line, y)| {
+        line.chars().zip(0..).filter_map(move |(chr, x)| match chr {
+            '#' => Some(Ok((x, y))),
+            '.' => None,
+            other => Some(Err(Day11Error::UnknownTile(other, (x, y)))),
+        })
+    }).collect()
+}
+
+/// Sums the distances between every pair of galaxies after every empty row and column has been
+/// replaced by `factor` empty rows or columns.
+///
+/// Rows and columns are handled independently. Along each axis, the coordinates are sorted so
+/// that every galaxy's expanded coordinate follows from how many occupied lines precede it, and
+/// the pairwise distances follow from a running prefix sum. This takes `O(n log n)` for `n`
+/// galaxies instead of looking at every pair.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day11::{ parse, distance_sum };
+///
+/// let galaxies = parse("...#......
+/// .......#..
+/// #.........
+/// ..........
+/// ......#...
+/// .#........
+/// .........#
+/// ..........
+/// .......#..
+/// #...#.....").unwrap();
+///
+/// assert_eq!(1030, distance_sum(&galaxies, 10));
+/// assert_eq!(8410, distance_sum(&galaxies, 100));
+/// ```
+pub fn distance_sum(galaxies: &[Coordinate], factor: u64) -> u64 {
+    let xs = galaxies.iter().map(|&(x, _)| x).collect();
+    let ys = galaxies.iter().map(|&(_, y)| y).collect();
+
+    axis_distance_sum(xs, factor) + axis_distance_sum(ys, factor)
+}
+
+fn axis_distance_sum(mut coordinates: Vec<u64>, factor: u64) -> u64 {
+    coordinates.sort_unstable();
+
+    let mut occupied_before = 0;
+    let mut previous = None;
+    let mut prefix_sum = 0;
+    let mut total = 0;
+
+    for (idx, &coordinate) in (0..).zip(&coordinates) {
+        if let Some(previous) = previous {
+            if previous != coordinate {
+                occupied_before += 1;
+            }
+        }
+        previous = Some(coordinate);
+
+        let expanded = occupied_before + factor * (coordinate - occupied_before);
+
+        total += expanded * idx - prefix_sum;
+        prefix_sum += expanded;
+    }
+
+    total
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day11::{ parse, part1 };
+/// assert_eq!(374, part1(&parse("...#......
+/// .......#..
+/// #.........
+/// ..........
+/// ......#...
+/// .#........
+/// .........#
+/// ..........
+/// .......#..
+/// #...#.....").unwrap()));
+/// ```
+#[aoc(day11, part1)]
+pub fn part1(galaxies: &[Coordinate]) -> u64 {
+    distance_sum(galaxies, 2)
+}
+
+#[aoc(day11, part2)]
+pub fn part2(galaxies: &[Coordinate]) -> u64 {
+    distance_sum(galaxies, 1_000_000)
+}
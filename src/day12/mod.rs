@@ -0,0 +1,205 @@
+pub mod nonogram;
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ branch::alt, character::complete::{ char, line_ending, multispace0, space1, u64 }, combinator::value, multi::{ many1, separated_list0, separated_list1 }, sequence::{ separated_pair, terminated }, Err, error::Error, IResult, Parser };
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day12Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Row {0} contains a group of zero damaged springs")]
+    EmptyGroup(usize),
+}
+
+impl Spring {
+    fn parser(input: &str) -> IResult<&str, Self> {
+        alt((
+            value(Spring::Operational, char('.')),
+            value(Spring::Damaged, char('#')),
+            value(Spring::Unknown, char('?')),
+        ))(input)
+    }
+}
+
+impl Row {
+    pub fn new(springs: Vec<Spring>, groups: Vec<usize>) -> Self {
+        Self { springs, groups }
+    }
+
+    pub fn springs(&self) -> &[Spring] {
+        &self.springs
+    }
+
+    pub fn groups(&self) -> &[usize] {
+        &self.groups
+    }
+
+    /// Repeats the row `multiplier` times, joining the copies of the springs with an unknown
+    /// spring.
+    pub fn unfold(&self, multiplier: usize) -> Self {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * multiplier);
+        for copy in 0..multiplier {
+            if copy > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+
+        Self { springs, groups: self.groups.repeat(multiplier) }
+    }
+
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day12::parse;
+    ///
+    /// let rows = parse("?###???????? 3,2,1").unwrap();
+    /// assert_eq!(10, rows[0].arrangements());
+    /// assert_eq!(506250, rows[0].unfold(5).arrangements());
+    /// ```
+    pub fn arrangements(&self) -> u128 {
+        count_arrangements(&self.springs, &self.groups)
+    }
+
+    fn parser(input: &str) -> IResult<&str, Self> {
+        separated_pair(
+            many1(Spring::parser),
+            space1,
+            separated_list1(char(','), u64.map(|group| group as usize)),
+        )
+            .map(|(springs, groups)| Row { springs, groups })
+            .parse(input)
+    }
+}
+
+/// Counts the ways in which the unknown springs can be filled in, such that the runs of damaged
+/// springs have exactly the lengths in `groups`, in order.
+///
+/// This is a tabulated dynamic program over (position, group index), taking `O(n · g)` time for
+/// `n` springs and `g` groups.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day12::{ count_arrangements, Spring::* };
+///
+/// assert_eq!(3, count_arrangements(&[Unknown, Unknown, Unknown, Operational, Damaged], &[1, 1]));
+/// assert_eq!(0, count_arrangements(&[Damaged, Damaged], &[1]));
+/// assert_eq!(1, count_arrangements(&[], &[]));
+/// ```
+pub fn count_arrangements(springs: &[Spring], groups: &[usize]) -> u128 {
+    let len = springs.len();
+    let width = groups.len() + 1;
+
+    // The number of springs, starting at each position, that could all be damaged.
+    let mut run = vec![0; len + 1];
+    for idx in (0..len).rev() {
+        if springs[idx] != Spring::Operational {
+            run[idx] = run[idx + 1] + 1;
+        }
+    }
+
+    // `ways[idx * width + group]` counts the arrangements of `springs[idx..]` into `groups[group..]`.
+    let mut ways = vec![0u128; (len + 1) * width];
+    ways[len * width + groups.len()] = 1;
+
+    for idx in (0..len).rev() {
+        for group in 0..width {
+            let mut total = 0;
+
+            if springs[idx] != Spring::Damaged {
+                total += ways[(idx + 1) * width + group];
+            }
+
+            if springs[idx] != Spring::Operational && group < groups.len() {
+                let size = groups[group];
+                let end = idx + size;
+
+                if run[idx] >= size && (end == len || springs[end] != Spring::Damaged) {
+                    total += ways[std::cmp::min(end + 1, len) * width + group + 1];
+                }
+            }
+
+            ways[idx * width + group] = total;
+        }
+    }
+
+    ways[0]
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day12::{ parse, Day12Error };
+///
+/// assert_eq!(2, parse("???.### 1,1,3
+/// .??..??...?##. 1,1,3").unwrap().len());
+/// assert!(matches!(parse("???.### 1,,3"), Err(Day12Error::Rest(_))));
+/// assert!(matches!(parse("???.### 1,0,3"), Err(Day12Error::EmptyGroup(0))));
+/// ```
+#[aoc_generator(day12)]
+pub fn parse(input: &str) -> Result<Vec<Row>, Day12Error> {
+    let rows = match terminated(separated_list0(line_ending, Row::parser), multispace0)(input) {
+        Ok(("", rows)) => rows,
+        Ok((rest, _)) => return Err(Day12Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day12Error::ParseError(err.to_owned())),
+    };
+
+    match rows.iter().position(|row| row.groups.contains(&0)) {
+        Some(idx) => Err(Day12Error::EmptyGroup(idx)),
+        None => Ok(rows),
+    }
+}
+
+/// Sums the arrangements of every row after unfolding it `multiplier` times.
+pub fn solve(rows: &[Row], multiplier: usize) -> u128 {
+    rows.iter().map(|row| row.unfold(multiplier).arrangements()).sum()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day12::{ parse, part1 };
+/// assert_eq!(21, part1(&parse("???.### 1,1,3
+/// .??..??...?##. 1,1,3
+/// ?#?#?#?#?#?#?#? 1,3,1,6
+/// ????.#...#... 4,1,1
+/// ????.######..#####. 1,6,5
+/// ?###???????? 3,2,1").unwrap()));
+/// ```
+#[aoc(day12, part1)]
+pub fn part1(rows: &[Row]) -> u128 {
+    solve(rows, 1)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day12::{ parse, part2 };
+/// assert_eq!(525152, part2(&parse("???.### 1,1,3
+/// .??..??...?##. 1,1,3
+/// ?#?#?#?#?#?#?#? 1,3,1,6
+/// ????.#...#... 4,1,1
+/// ????.######..#####. 1,6,5
+/// ?###???????? 3,2,1").unwrap()));
+/// ```
+#[aoc(day12, part2)]
+pub fn part2(rows: &[Row]) -> u128 {
+    solve(rows, 5)
+}
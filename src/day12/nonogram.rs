@@ -0,0 +1,291 @@
+use nom::{ character::complete::{ line_ending, multispace0 }, multi::{ many1, separated_list1 }, sequence::{ separated_pair, terminated }, Err, error::Error, IResult };
+use thiserror::Error;
+
+use super::{ count_arrangements, Row, Spring };
+
+type Coordinate = (usize, usize);
+
+/// A fully filled in nonogram, indexed as `grid[y][x]`. Filled cells are `Spring::Damaged`, blank
+/// cells are `Spring::Operational`.
+pub type Grid = Vec<Vec<Spring>>;
+
+/// A two-dimensional nonogram: every row and every column is a day 12 row of its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    rows: Vec<Vec<usize>>,
+    columns: Vec<Vec<usize>>,
+    /// The cells that are given up front, `Spring::Unknown` everywhere else.
+    given: Grid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    None,
+    Unique(Grid),
+    /// The puzzle has more than one solution, two of which are given.
+    Multiple(Grid, Grid),
+}
+
+#[derive(Debug, Error)]
+pub enum NonogramError {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Expected {expected} cells in line {line}, found {found}")]
+    DimensionMismatch { line: usize, expected: usize, found: usize },
+    #[error("The row and column patterns disagree about cell {0:?}")]
+    Conflict(Coordinate),
+}
+
+/// Raised by line solving when a line can no longer be completed.
+struct Contradiction;
+
+impl Nonogram {
+    /// Creates an empty nonogram from its row and column clues. A clue of `0` stands for an
+    /// empty line, just like no clue at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day12::nonogram::{ render, Nonogram, Solutions };
+    ///
+    /// let nonogram = Nonogram::new(vec![vec![0], vec![2]], vec![vec![1], vec![1]]);
+    /// match nonogram.solve() {
+    ///     Solutions::Unique(grid) => assert_eq!("..\n##\n", render(&grid)),
+    ///     other => panic!("expected a unique solution, found {other:?}"),
+    /// }
+    /// ```
+    pub fn new(rows: Vec<Vec<usize>>, columns: Vec<Vec<usize>>) -> Self {
+        let given = vec![vec![Spring::Unknown; columns.len()]; rows.len()];
+
+        Self { rows: without_empty_groups(rows), columns: without_empty_groups(columns), given }
+    }
+
+    /// Solves the nonogram, reporting whether it has no, one, or multiple solutions.
+    ///
+    /// Every row and column is repeatedly narrowed down with the day 12 arrangement counter: a
+    /// cell is fixed as soon as only one of its two values still leaves arrangements for its line.
+    /// Once that no longer makes progress, the solver guesses a cell and backtracks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day12::nonogram::{ parse, render, Solutions };
+    ///
+    /// let cross = parse("??? 1
+    /// ??? 3
+    /// ??? 1
+    ///
+    /// ??? 1
+    /// ??? 3
+    /// ??? 1").unwrap();
+    /// match cross.solve() {
+    ///     Solutions::Unique(grid) => assert_eq!(".#.\n###\n.#.\n", render(&grid)),
+    ///     other => panic!("expected a unique solution, found {other:?}"),
+    /// }
+    ///
+    /// let diagonal = parse("?? 1
+    /// ?? 1
+    ///
+    /// ?? 1
+    /// ?? 1").unwrap();
+    /// assert!(matches!(diagonal.solve(), Solutions::Multiple(_, _)));
+    ///
+    /// let hinted = parse("#? 1
+    /// ?? 1
+    ///
+    /// ?? 1
+    /// ?? 1").unwrap();
+    /// assert!(matches!(hinted.solve(), Solutions::Unique(_)));
+    ///
+    /// let impossible = parse("?? 2
+    /// ?? 2
+    ///
+    /// ?? 1
+    /// ?? 1").unwrap();
+    /// assert_eq!(Solutions::None, impossible.solve());
+    /// ```
+    pub fn solve(&self) -> Solutions {
+        let mut found = Vec::with_capacity(2);
+        self.search(self.given.clone(), &mut found);
+
+        let mut found = found.into_iter();
+        match (found.next(), found.next()) {
+            (None, _) => Solutions::None,
+            (Some(grid), None) => Solutions::Unique(grid),
+            (Some(first), Some(second)) => Solutions::Multiple(first, second),
+        }
+    }
+
+    fn search(&self, mut grid: Grid, found: &mut Vec<Grid>) {
+        if self.propagate(&mut grid).is_err() {
+            return;
+        }
+
+        let unknown = grid.iter().enumerate().find_map(|(y, row)| {
+            row.iter().position(|&cell| cell == Spring::Unknown).map(|x| (x, y))
+        });
+
+        match unknown {
+            None => found.push(grid),
+            Some((x, y)) => {
+                for guess in [Spring::Damaged, Spring::Operational] {
+                    if found.len() >= 2 {
+                        return;
+                    }
+
+                    let mut attempt = grid.clone();
+                    attempt[y][x] = guess;
+                    self.search(attempt, found);
+                }
+            },
+        }
+    }
+
+    /// Applies line solving to every row and column until nothing changes anymore.
+    fn propagate(&self, grid: &mut Grid) -> Result<(), Contradiction> {
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for (y, clue) in self.rows.iter().enumerate() {
+                let line = grid[y].clone();
+                for (x, cell) in solve_line(&line, clue)?.into_iter().enumerate() {
+                    changed |= grid[y][x] != cell;
+                    grid[y][x] = cell;
+                }
+            }
+
+            for (x, clue) in self.columns.iter().enumerate() {
+                let line: Vec<Spring> = grid.iter().map(|row| row[x]).collect();
+                for (y, cell) in solve_line(&line, clue)?.into_iter().enumerate() {
+                    changed |= grid[y][x] != cell;
+                    grid[y][x] = cell;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fixes every unknown cell of a line that has the same value in all of its arrangements.
+fn solve_line(line: &[Spring], clue: &[usize]) -> Result<Vec<Spring>, Contradiction> {
+    if count_arrangements(line, clue) == 0 {
+        return Err(Contradiction);
+    }
+
+    let mut solved = line.to_vec();
+    let mut attempt = line.to_vec();
+
+    for idx in 0..line.len() {
+        if line[idx] != Spring::Unknown {
+            continue;
+        }
+
+        attempt[idx] = Spring::Damaged;
+        let filled = count_arrangements(&attempt, clue) > 0;
+        attempt[idx] = Spring::Operational;
+        let blank = count_arrangements(&attempt, clue) > 0;
+        attempt[idx] = Spring::Unknown;
+
+        match (filled, blank) {
+            (true, false) => solved[idx] = Spring::Damaged,
+            (false, true) => solved[idx] = Spring::Operational,
+            _ => {},
+        }
+    }
+
+    Ok(solved)
+}
+
+/// Draws a grid with `#` for filled and `.` for blank cells, one line per row.
+pub fn render(grid: &[Vec<Spring>]) -> String {
+    grid.iter().fold(String::new(), |mut acc, row| {
+        acc.extend(row.iter().map(|cell| match cell {
+            Spring::Damaged => '#',
+            Spring::Operational => '.',
+            Spring::Unknown => '?',
+        }));
+        acc.push('\n');
+        acc
+    })
+}
+
+fn lines(input: &str) -> IResult<&str, (Vec<Row>, Vec<Row>)> {
+    terminated(
+        separated_pair(
+            separated_list1(line_ending, Row::parser),
+            many1(line_ending),
+            separated_list1(line_ending, Row::parser),
+        ),
+        multispace0,
+    )(input)
+}
+
+/// Parses a nonogram from its rows and columns in the day 12 format, separated by a blank line.
+///
+/// The springs of each line hold the cells that are given up front, and a clue of `0` stands for
+/// a line without any filled cells.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day12::nonogram::{ parse, NonogramError };
+///
+/// assert!(matches!(parse("??? 1
+/// ?? 1
+///
+/// ?? 1
+/// ?? 1"), Err(NonogramError::DimensionMismatch { line: 0, expected: 2, found: 3 })));
+///
+/// assert!(matches!(parse("#? 1
+/// ?? 1
+///
+/// .? 1
+/// ?? 1"), Err(NonogramError::Conflict((0, 0)))));
+/// ```
+pub fn parse(input: &str) -> Result<Nonogram, NonogramError> {
+    let (rows, columns) = match lines(input) {
+        Ok(("", result)) => result,
+        Ok((rest, _)) => return Err(NonogramError::Rest(rest.to_owned())),
+        Err(err) => return Err(NonogramError::ParseError(err.to_owned())),
+    };
+
+    let (width, height) = (columns.len(), rows.len());
+    for (line, (row, expected)) in rows.iter().map(|row| (row, width))
+        .chain(columns.iter().map(|column| (column, height)))
+        .enumerate()
+    {
+        if row.springs().len() != expected {
+            return Err(NonogramError::DimensionMismatch { line, expected, found: row.springs().len() });
+        }
+    }
+
+    let mut given: Grid = rows.iter().map(|row| row.springs().to_vec()).collect();
+    for (x, column) in columns.iter().enumerate() {
+        for (y, &cell) in column.springs().iter().enumerate() {
+            match (given[y][x], cell) {
+                (_, Spring::Unknown) => {},
+                (Spring::Unknown, _) => given[y][x] = cell,
+                (existing, _) if existing != cell => return Err(NonogramError::Conflict((x, y))),
+                _ => {},
+            }
+        }
+    }
+
+    let clues = |lines: Vec<Row>| without_empty_groups(lines.iter().map(|line| line.groups().to_vec()).collect());
+
+    Ok(Nonogram { rows: clues(rows), columns: clues(columns), given })
+}
+
+/// Drops the groups of length `0`, which the arrangement counter would otherwise count as
+/// groups of their own.
+fn without_empty_groups(lines: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    lines.into_iter().map(|mut groups| {
+        groups.retain(|&group| group > 0);
+        groups
+    }).collect()
+}
@@ -0,0 +1,208 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ character::complete::{ line_ending, multispace0, one_of }, multi::{ many1, separated_list1 }, sequence::{ pair, terminated }, Err, error::Error, IResult };
+use thiserror::Error;
+
+/// A pattern of ash and rocks, stored as one bitmask per row and one per column, where set bits
+/// are rocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// A horizontal line of reflection between two rows.
+    Horizontal,
+    /// A vertical line of reflection between two columns.
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reflection {
+    pub axis: Axis,
+    /// The number of rows above, or columns left of, the line of reflection.
+    pub index: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum Day13Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Pattern {0} is wider or taller than 64 tiles")]
+    TooLarge(usize),
+    #[error("Pattern {0} is not rectangular")]
+    Ragged(usize),
+    #[error("Pattern {pattern} has no reflection with {smudges} smudges")]
+    NoReflection { pattern: usize, smudges: u32 },
+}
+
+impl Reflection {
+    /// The number this reflection contributes to the puzzle answer.
+    pub fn summary(&self) -> usize {
+        match self.axis {
+            Axis::Vertical => self.index,
+            Axis::Horizontal => 100 * self.index,
+        }
+    }
+}
+
+impl Pattern {
+    fn new(lines: Vec<Vec<char>>) -> Option<Self> {
+        let width = lines.first().map_or(0, Vec::len);
+        if lines.iter().any(|line| line.len() != width) {
+            return None;
+        }
+
+        let mut rows = vec![0u64; lines.len()];
+        let mut columns = vec![0u64; width];
+        for (y, line) in lines.iter().enumerate() {
+            for (x, _) in line.iter().enumerate().filter(|(_, &chr)| chr == '#') {
+                rows[y] |= 1 << x;
+                columns[x] |= 1 << y;
+            }
+        }
+
+        Some(Self { rows, columns })
+    }
+
+    /// Lists every line of reflection for which exactly `smudges` tiles differ from their mirror
+    /// image. Vertical lines are listed before horizontal ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day13::{ parse, Axis, Reflection };
+    ///
+    /// let patterns = parse(&[
+    ///     "#.##..##.",
+    ///     "..#.##.#.",
+    ///     "##......#",
+    ///     "##......#",
+    ///     "..#.##.#.",
+    ///     "..##..##.",
+    ///     "#.#.##.#.",
+    /// ].join("\n")).unwrap();
+    ///
+    /// assert_eq!(vec![Reflection { axis: Axis::Vertical, index: 5 }], patterns[0].reflections(0));
+    /// assert_eq!(vec![Reflection { axis: Axis::Horizontal, index: 3 }], patterns[0].reflections(1));
+    /// ```
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let vertical = mirror_lines(&self.columns, smudges)
+            .map(|index| Reflection { axis: Axis::Vertical, index });
+        let horizontal = mirror_lines(&self.rows, smudges)
+            .map(|index| Reflection { axis: Axis::Horizontal, index });
+
+        vertical.chain(horizontal).collect()
+    }
+
+    /// The first line of reflection with exactly `smudges` smudges, if any.
+    pub fn reflection(&self, smudges: u32) -> Option<Reflection> {
+        self.reflections(smudges).into_iter().next()
+    }
+}
+
+/// Finds every split of `lines` where the lines mirror each other up to `smudges` differing bits.
+fn mirror_lines(lines: &[u64], smudges: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |&split| {
+        let differences: u32 = lines[..split].iter().rev()
+            .zip(&lines[split..])
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum();
+
+        differences == smudges
+    })
+}
+
+fn patterns(input: &str) -> IResult<&str, Vec<Vec<Vec<char>>>> {
+    terminated(
+        separated_list1(
+            pair(line_ending, line_ending),
+            separated_list1(line_ending, many1(one_of("#.")))),
+        multispace0,
+    )(input)
+}
+
+#[aoc_generator(day13)]
+pub fn parse(input: &str) -> Result<Vec<Pattern>, Day13Error> {
+    let patterns = match patterns(input) {
+        Ok(("", patterns)) => patterns,
+        Ok((rest, _)) => return Err(Day13Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day13Error::ParseError(err.to_owned())),
+    };
+
+    patterns.into_iter().enumerate().map(|(idx, lines)| {
+        if lines.len() > 64 || lines.iter().any(|line| line.len() > 64) {
+            return Err(Day13Error::TooLarge(idx));
+        }
+
+        Pattern::new(lines).ok_or(Day13Error::Ragged(idx))
+    }).collect()
+}
+
+/// Summarizes the first reflection of every pattern with exactly `smudges` smudges.
+pub fn solve(patterns: &[Pattern], smudges: u32) -> Result<usize, Day13Error> {
+    patterns.iter().enumerate().map(|(pattern, tiles)| {
+        tiles.reflection(smudges)
+            .map(|reflection| reflection.summary())
+            .ok_or(Day13Error::NoReflection { pattern, smudges })
+    }).sum()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day13::{ parse, part1 };
+/// let input = [
+///     "#.##..##.",
+///     "..#.##.#.",
+///     "##......#",
+///     "##......#",
+///     "..#.##.#.",
+///     "..##..##.",
+///     "#.#.##.#.",
+///     "",
+///     "#...##..#",
+///     "#....#..#",
+///     "..##..###",
+///     "#####.##.",
+///     "#####.##.",
+///     "..##..###",
+///     "#....#..#",
+/// ].join("\n");
+/// assert_eq!(405, part1(&parse(&input).unwrap()).unwrap());
+/// ```
+#[aoc(day13, part1)]
+pub fn part1(patterns: &[Pattern]) -> Result<usize, Day13Error> {
+    solve(patterns, 0)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day13::{ parse, part2 };
+/// let input = [
+///     "#.##..##.",
+///     "..#.##.#.",
+///     "##......#",
+///     "##......#",
+///     "..#.##.#.",
+///     "..##..##.",
+///     "#.#.##.#.",
+///     "",
+///     "#...##..#",
+///     "#....#..#",
+///     "..##..###",
+///     "#####.##.",
+///     "#####.##.",
+///     "..##..###",
+///     "#....#..#",
+/// ].join("\n");
+/// assert_eq!(400, part2(&parse(&input).unwrap()).unwrap());
+/// ```
+#[aoc(day13, part2)]
+pub fn part2(patterns: &[Pattern]) -> Result<usize, Day13Error> {
+    solve(patterns, 1)
+}
@@ -0,0 +1,156 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+use crate::{ cycle::nth_state, grid::{ Grid, GridError } };
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    tiles: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+#[derive(Debug, Error)]
+pub enum Day14Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("The platform has no tiles")]
+    Empty,
+}
+
+const ROUND: u8 = b'O';
+const CUBE: u8 = b'#';
+const EMPTY: u8 = b'.';
+
+/// The order in which a single spin cycle tilts the platform.
+pub const SPIN_CYCLE: [Direction; 4] = [Direction::North, Direction::West, Direction::South, Direction::East];
+
+impl Platform {
+    /// Rolls every round rock as far as it goes in `direction`.
+    pub fn tilt(&mut self, direction: Direction) {
+        let (width, height) = (self.width, self.height);
+        let (lanes, length) = match direction {
+            Direction::North | Direction::South => (width, height),
+            Direction::West | Direction::East => (height, width),
+        };
+
+        for lane in 0..lanes {
+            // Maps the n-th tile along the lane, counted from the side the rocks roll towards, to
+            // its index in `tiles`.
+            let index = |n: usize| match direction {
+                Direction::North => n * width + lane,
+                Direction::South => (height - 1 - n) * width + lane,
+                Direction::West => lane * width + n,
+                Direction::East => lane * width + width - 1 - n,
+            };
+
+            let mut free = 0;
+            for n in 0..length {
+                match self.tiles[index(n)] {
+                    CUBE => free = n + 1,
+                    ROUND => {
+                        self.tiles[index(n)] = EMPTY;
+                        self.tiles[index(free)] = ROUND;
+                        free += 1;
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+
+    /// Tilts the platform north, west, south and east, in that order.
+    pub fn spin(&mut self) {
+        for direction in SPIN_CYCLE {
+            self.tilt(direction);
+        }
+    }
+
+    /// The total load on the north support beams.
+    pub fn north_load(&self) -> usize {
+        self.tiles.chunks(self.width)
+            .zip((1..=self.height).rev())
+            .map(|(row, load)| row.iter().filter(|&&tile| tile == ROUND).count() * load)
+            .sum()
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::{ day14::{ parse, Day14Error }, grid::GridError };
+///
+/// assert!(matches!(parse(""), Err(Day14Error::Empty)));
+/// assert!(matches!(parse("\n\n"), Err(Day14Error::Empty)));
+/// assert!(matches!(parse("O.\n#"), Err(Day14Error::Grid(GridError::Ragged(1)))));
+/// ```
+#[aoc_generator(day14)]
+pub fn parse(input: &str) -> Result<Platform, Day14Error> {
+    let Grid { tiles, width, height } = Grid::parse(input, |chr| {
+        u8::try_from(chr).ok().filter(|tile| matches!(*tile, ROUND | CUBE | EMPTY))
+    })?;
+
+    // Without any tiles there are no rows to chunk the platform into.
+    if tiles.is_empty() {
+        return Err(Day14Error::Empty);
+    }
+
+    Ok(Platform { tiles, width, height })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day14::{ parse, part1 };
+/// assert_eq!(136, part1(&parse("O....#....
+/// O.OO#....#
+/// .....##...
+/// OO.#O....O
+/// .O.....O#.
+/// O.#..O.#.#
+/// ..O..#O..O
+/// .......O..
+/// #....###..
+/// #OO..#....").unwrap()));
+/// ```
+#[aoc(day14, part1)]
+pub fn part1(platform: &Platform) -> usize {
+    let mut platform = platform.clone();
+    platform.tilt(Direction::North);
+
+    platform.north_load()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day14::{ parse, part2 };
+/// assert_eq!(64, part2(&parse("O....#....
+/// O.OO#....#
+/// .....##...
+/// OO.#O....O
+/// .O.....O#.
+/// O.#..O.#.#
+/// ..O..#O..O
+/// .......O..
+/// #....###..
+/// #OO..#....").unwrap()));
+/// ```
+#[aoc(day14, part2)]
+pub fn part2(platform: &Platform) -> usize {
+    let spun = nth_state(platform.clone(), |platform| {
+        let mut next = platform.clone();
+        next.spin();
+        next
+    }, 1_000_000_000);
+
+    spun.north_load()
+}
@@ -0,0 +1,251 @@
+use std::hash::{ BuildHasher, Hasher };
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ branch::alt, character::complete::{ alpha1, char, u8 }, combinator::value, multi::separated_list0, sequence::{ pair, preceded }, Err, error::Error, IResult, Parser };
+use thiserror::Error;
+
+/// The Holiday ASCII String Helper algorithm, as a `Hasher`.
+///
+/// Note that `Hash` implementations of `str` and slices write more than just their bytes, so to
+/// get the HASH of a label, feed its bytes to [`Hasher::write`] directly, or use [`hash`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HolidayHasher {
+    state: u8,
+}
+
+/// Builds [`HolidayHasher`]s, so that HASH can be plugged into hash-based collections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildHolidayHasher;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Remove,
+    Insert(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Step {
+    label: String,
+    operation: Operation,
+}
+
+/// The HASHMAP: 256 boxes of lenses, where the order of the lenses in a box is the order in which
+/// they were inserted.
+#[derive(Debug, Clone)]
+pub struct LensBoxes<H = BuildHolidayHasher> {
+    boxes: Vec<Vec<(String, u8)>>,
+    hasher: H,
+}
+
+#[derive(Debug, Error)]
+pub enum Day15Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+}
+
+const BOXES: usize = 256;
+
+impl Hasher for HolidayHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = self.state.wrapping_add(byte).wrapping_mul(17);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state as u64
+    }
+}
+
+impl BuildHasher for BuildHolidayHasher {
+    type Hasher = HolidayHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        HolidayHasher::default()
+    }
+}
+
+/// Runs the HASH algorithm on a string.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day15::hash;
+/// assert_eq!(52, hash("HASH"));
+/// assert_eq!(0, hash("rn"));
+/// ```
+pub fn hash(input: &str) -> u8 {
+    hash_with(&BuildHolidayHasher, input) as u8
+}
+
+fn hash_with(builder: &impl BuildHasher, input: &str) -> u64 {
+    let mut hasher = builder.build_hasher();
+    hasher.write(input.as_bytes());
+    hasher.finish()
+}
+
+impl LensBoxes {
+    pub fn new() -> Self {
+        Self::with_hasher(BuildHolidayHasher)
+    }
+}
+
+impl Default for LensBoxes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: BuildHasher> LensBoxes<H> {
+    /// Creates empty boxes that distribute the lenses using `hasher`.
+    pub fn with_hasher(hasher: H) -> Self {
+        Self { boxes: vec![Vec::new(); BOXES], hasher }
+    }
+
+    /// The box a lens with the given label goes into.
+    pub fn box_of(&self, label: &str) -> usize {
+        (hash_with(&self.hasher, label) % BOXES as u64) as usize
+    }
+
+    /// Puts a lens into its box, replacing the lens with the same label in place if there is one.
+    /// Returns the focal length of the replaced lens.
+    pub fn insert(&mut self, label: &str, focal_length: u8) -> Option<u8> {
+        let box_number = self.box_of(label);
+        let lenses = &mut self.boxes[box_number];
+
+        match lenses.iter_mut().find(|(existing, _)| existing == label) {
+            Some((_, existing)) => Some(std::mem::replace(existing, focal_length)),
+            None => {
+                lenses.push((label.to_owned(), focal_length));
+                None
+            },
+        }
+    }
+
+    /// Takes the lens with the given label out of its box, moving the lenses behind it forward.
+    /// Returns its focal length.
+    pub fn remove(&mut self, label: &str) -> Option<u8> {
+        let box_number = self.box_of(label);
+        let lenses = &mut self.boxes[box_number];
+        let idx = lenses.iter().position(|(existing, _)| existing == label)?;
+
+        Some(lenses.remove(idx).1)
+    }
+
+    /// The focal length of the lens with the given label.
+    pub fn get(&self, label: &str) -> Option<u8> {
+        self.boxes[self.box_of(label)].iter()
+            .find(|(existing, _)| existing == label)
+            .map(|&(_, focal_length)| focal_length)
+    }
+
+    /// The lenses in a box, front to back.
+    pub fn lenses(&self, box_number: usize) -> impl Iterator<Item = (&str, u8)> {
+        self.boxes[box_number].iter().map(|(label, focal_length)| (label.as_str(), *focal_length))
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        match step.operation {
+            Operation::Remove => { self.remove(&step.label); },
+            Operation::Insert(focal_length) => { self.insert(&step.label, focal_length); },
+        }
+    }
+
+    /// The focusing power of the lens with the given label, if it is in one of the boxes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day15::LensBoxes;
+    ///
+    /// let mut boxes = LensBoxes::new();
+    /// boxes.insert("rn", 1);
+    /// boxes.insert("cm", 2);
+    /// boxes.insert("ot", 9);
+    /// boxes.insert("ab", 5);
+    /// boxes.insert("pc", 6);
+    ///
+    /// assert_eq!(Some(4), boxes.lens_focusing_power("cm"));
+    /// assert_eq!(Some(36), boxes.lens_focusing_power("ot"));
+    ///
+    /// boxes.remove("cm");
+    /// assert_eq!(None, boxes.lens_focusing_power("cm"));
+    /// ```
+    pub fn lens_focusing_power(&self, label: &str) -> Option<usize> {
+        let box_number = self.box_of(label);
+        let slot = self.boxes[box_number].iter().position(|(existing, _)| existing == label)?;
+
+        Some((box_number + 1) * (slot + 1) * self.boxes[box_number][slot].1 as usize)
+    }
+
+    /// The sum of the focusing powers of all lenses.
+    pub fn focusing_power(&self) -> usize {
+        self.boxes.iter().zip(1..).map(|(lenses, box_number)| {
+            lenses.iter().zip(1..).map(|((_, focal_length), slot)| {
+                box_number * slot * *focal_length as usize
+            }).sum::<usize>()
+        }).sum()
+    }
+}
+
+impl Step {
+    fn parser(input: &str) -> IResult<&str, Self> {
+        pair(
+            alpha1,
+            alt((
+                value(Operation::Remove, char('-')),
+                preceded(char('='), u8).map(Operation::Insert),
+            )),
+        )
+            .map(|(label, operation): (&str, _)| Step { label: label.to_owned(), operation })
+            .parse(input)
+    }
+}
+
+/// Splits the initialization sequence into its steps, ignoring newlines.
+#[aoc_generator(day15, part1)]
+pub fn split(input: &str) -> Vec<String> {
+    input.replace(['\n', '\r'], "").split(',').map(str::to_owned).collect()
+}
+
+fn steps(input: &str) -> IResult<&str, Vec<Step>> {
+    separated_list0(char(','), Step::parser)(input)
+}
+
+#[aoc_generator(day15, part2)]
+pub fn parse(input: &str) -> Result<Vec<Step>, Day15Error> {
+    match steps(&input.replace(['\n', '\r'], "")) {
+        Ok(("", steps)) => Ok(steps),
+        Ok((rest, _)) => Err(Day15Error::Rest(rest.to_owned())),
+        Err(err) => Err(Day15Error::ParseError(err.to_owned())),
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day15::{ split, part1 };
+/// assert_eq!(1320, part1(&split("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7")));
+/// ```
+#[aoc(day15, part1)]
+pub fn part1(steps: &[String]) -> usize {
+    steps.iter().map(|step| hash(step) as usize).sum()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day15::{ parse, part2 };
+/// assert_eq!(145, part2(&parse("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap()));
+/// ```
+#[aoc(day15, part2)]
+pub fn part2(steps: &[Step]) -> usize {
+    let mut boxes = LensBoxes::new();
+    for step in steps {
+        boxes.apply(step);
+    }
+
+    boxes.focusing_power()
+}
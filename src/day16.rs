@@ -0,0 +1,217 @@
+use std::thread;
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+use crate::grid::{ Grid, GridError };
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contraption {
+    tiles: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// A beam entering the contraption at `position`, travelling in `heading`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Entry {
+    pub position: (usize, usize),
+    pub heading: Direction,
+}
+
+#[derive(Debug, Error)]
+pub enum Day16Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+}
+
+impl Direction {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+
+    /// The directions a beam travels in after hitting `tile` while heading in this direction.
+    fn through(self, tile: u8) -> (Direction, Option<Direction>) {
+        use Direction::*;
+
+        match (tile, self) {
+            (b'/', North) => (East, None),
+            (b'/', East) => (North, None),
+            (b'/', South) => (West, None),
+            (b'/', West) => (South, None),
+            (b'\\', North) => (West, None),
+            (b'\\', West) => (North, None),
+            (b'\\', South) => (East, None),
+            (b'\\', East) => (South, None),
+            (b'|', East | West) => (North, Some(South)),
+            (b'-', North | South) => (East, Some(West)),
+            _ => (self, None),
+        }
+    }
+}
+
+impl Contraption {
+    fn step(&self, (x, y): (usize, usize), heading: Direction) -> Option<(usize, usize)> {
+        match heading {
+            Direction::North => Some((x, y.checked_sub(1)?)),
+            Direction::East => (x + 1 < self.width).then_some((x + 1, y)),
+            Direction::South => (y + 1 < self.height).then_some((x, y + 1)),
+            Direction::West => Some((x.checked_sub(1)?, y)),
+        }
+    }
+
+    /// Counts the tiles a beam passes through. A beam entering outside of the contraption does
+    /// not pass through any.
+    ///
+    /// Every tile remembers the directions in which a beam already passed it, so that beams which
+    /// end up going in circles are only followed once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day16::{ parse, Direction, Entry };
+    ///
+    /// let contraption = parse(r".|...\....
+    /// |.-.\.....
+    /// .....|-...
+    /// ........|.
+    /// ..........
+    /// .........\
+    /// ..../.\\..
+    /// .-.-/..|..
+    /// .|....-|.\
+    /// ..//.|....").unwrap();
+    ///
+    /// assert_eq!(51, contraption.energized(Entry { position: (3, 0), heading: Direction::South }));
+    /// assert_eq!(0, contraption.energized(Entry { position: (10, 0), heading: Direction::West }));
+    ///
+    /// let empty = parse("").unwrap();
+    /// assert_eq!(0, empty.energized(Entry { position: (0, 0), heading: Direction::East }));
+    /// ```
+    pub fn energized(&self, entry: Entry) -> usize {
+        if entry.position.0 >= self.width || entry.position.1 >= self.height {
+            return 0;
+        }
+
+        let mut seen = vec![0u8; self.tiles.len()];
+        let mut beams = vec![(entry.position, entry.heading)];
+
+        while let Some((position, heading)) = beams.pop() {
+            let idx = position.1 * self.width + position.0;
+            if seen[idx] & heading.bit() != 0 {
+                continue;
+            }
+            seen[idx] |= heading.bit();
+
+            let (first, second) = heading.through(self.tiles[idx]);
+            for heading in std::iter::once(first).chain(second) {
+                if let Some(next) = self.step(position, heading) {
+                    beams.push((next, heading));
+                }
+            }
+        }
+
+        seen.iter().filter(|&&directions| directions != 0).count()
+    }
+
+    /// Every way a beam can enter the contraption from one of its edges.
+    pub fn entries(&self) -> Vec<Entry> {
+        if self.tiles.is_empty() {
+            return Vec::new();
+        }
+
+        let (right, bottom) = (self.width - 1, self.height - 1);
+
+        (0..self.width).flat_map(|x| [
+            Entry { position: (x, 0), heading: Direction::South },
+            Entry { position: (x, bottom), heading: Direction::North },
+        ]).chain((0..self.height).flat_map(|y| [
+            Entry { position: (0, y), heading: Direction::East },
+            Entry { position: (right, y), heading: Direction::West },
+        ])).collect()
+    }
+
+    /// Tries every entry point, spread over all available cores, and returns the one that
+    /// energizes the most tiles together with that number of tiles.
+    pub fn best_entry(&self) -> Option<(Entry, usize)> {
+        let entries = self.entries();
+        let threads = thread::available_parallelism().map_or(1, usize::from);
+        let chunk_size = std::cmp::max(1, entries.len().div_ceil(threads));
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = entries.chunks(chunk_size).map(|chunk| {
+                scope.spawn(move || {
+                    chunk.iter()
+                        .map(|&entry| (entry, self.energized(entry)))
+                        .max_by_key(|&(_, energized)| energized)
+                })
+            }).collect();
+
+            workers.into_iter()
+                .filter_map(|worker| worker.join().expect("beam tracer panicked"))
+                .max_by_key(|&(_, energized)| energized)
+        })
+    }
+}
+
+#[aoc_generator(day16)]
+pub fn parse(input: &str) -> Result<Contraption, Day16Error> {
+    let Grid { tiles, width, height } = Grid::parse(input, |chr| match chr {
+        '.' | '/' | '\\' | '|' | '-' => Some(chr as u8),
+        _ => None,
+    })?;
+
+    Ok(Contraption { tiles, width, height })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day16::{ parse, part1 };
+/// assert_eq!(46, part1(&parse(r".|...\....
+/// |.-.\.....
+/// .....|-...
+/// ........|.
+/// ..........
+/// .........\
+/// ..../.\\..
+/// .-.-/..|..
+/// .|....-|.\
+/// ..//.|....").unwrap()));
+/// ```
+#[aoc(day16, part1)]
+pub fn part1(contraption: &Contraption) -> usize {
+    contraption.energized(Entry { position: (0, 0), heading: Direction::East })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day16::{ parse, part2, Direction, Entry };
+///
+/// let contraption = parse(r".|...\....
+/// |.-.\.....
+/// .....|-...
+/// ........|.
+/// ..........
+/// .........\
+/// ..../.\\..
+/// .-.-/..|..
+/// .|....-|.\
+/// ..//.|....").unwrap();
+///
+/// assert_eq!(51, part2(&contraption));
+/// assert_eq!(Some((Entry { position: (3, 0), heading: Direction::South }, 51)), contraption.best_entry());
+/// ```
+#[aoc(day16, part2)]
+pub fn part2(contraption: &Contraption) -> usize {
+    contraption.best_entry().map_or(0, |(_, energized)| energized)
+}
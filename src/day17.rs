@@ -0,0 +1,243 @@
+use std::{ cmp::Reverse, collections::BinaryHeap };
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+use crate::grid::{ Grid, GridError };
+
+type Coordinate = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct City {
+    heat_loss: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+/// How far a crucible has to, and may, travel in a straight line before it turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crucible {
+    pub min_run: usize,
+    pub max_run: usize,
+}
+
+/// The cheapest way from the top-left to the bottom-right block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: u32,
+    /// Every block the crucible passes, including the first and the last one.
+    pub path: Vec<Coordinate>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day17Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("There is no route through the city for this crucible")]
+    NoRoute,
+}
+
+/// The axis along which the crucible made its last run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Horizontal = 0,
+    Vertical = 1,
+}
+
+impl Crucible {
+    pub const NORMAL: Crucible = Crucible { min_run: 1, max_run: 3 };
+    pub const ULTRA: Crucible = Crucible { min_run: 4, max_run: 10 };
+}
+
+impl City {
+    fn node(&self, (x, y): Coordinate, axis: Axis) -> usize {
+        (y * self.width + x) * 2 + axis as usize
+    }
+
+    fn block(&self, node: usize) -> Coordinate {
+        ((node / 2) % self.width, (node / 2) / self.width)
+    }
+
+    /// Finds the route with the least heat loss using Dijkstra's algorithm.
+    ///
+    /// Instead of tracking how far the crucible has gone straight, every edge of the graph is a
+    /// complete run followed by a turn, so a node is just a block and the axis of the last run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day17::{ parse, Crucible };
+    ///
+    /// let city = parse("111111111111
+    /// 999999999991
+    /// 999999999991
+    /// 999999999991
+    /// 999999999991").unwrap();
+    ///
+    /// let route = city.route(Crucible::ULTRA).unwrap();
+    /// assert_eq!(71, route.heat_loss);
+    /// assert_eq!(16, route.path.len());
+    /// assert_eq!("1>>>>>>>1111
+    /// 9999999v9991
+    /// 9999999v9991
+    /// 9999999v9991
+    /// 9999999v>>>>
+    /// ", city.render(&route.path));
+    ///
+    /// let empty = parse("").unwrap();
+    /// assert!(empty.route(Crucible::ULTRA).is_none());
+    /// assert_eq!("", empty.render(&[]));
+    /// ```
+    pub fn route(&self, crucible: Crucible) -> Option<Route> {
+        let target = (self.width.checked_sub(1)?, self.height.checked_sub(1)?);
+        let mut best = vec![u32::MAX; self.heat_loss.len() * 2];
+        let mut previous = vec![usize::MAX; self.heat_loss.len() * 2];
+        let mut queue = BinaryHeap::new();
+
+        for axis in [Axis::Horizontal, Axis::Vertical] {
+            let node = self.node((0, 0), axis);
+            best[node] = 0;
+            queue.push(Reverse((0, node)));
+        }
+
+        while let Some(Reverse((cost, node))) = queue.pop() {
+            if cost > best[node] {
+                continue;
+            }
+
+            let position = self.block(node);
+            if position == target {
+                return Some(Route { heat_loss: cost, path: self.unwind(node, &previous) });
+            }
+
+            let axis = if node % 2 == 0 { Axis::Vertical } else { Axis::Horizontal };
+            let directions: [(isize, isize); 2] = match axis {
+                Axis::Horizontal => [(1, 0), (-1, 0)],
+                Axis::Vertical => [(0, 1), (0, -1)],
+            };
+
+            for (dx, dy) in directions {
+                let mut cost = cost;
+                for run in 1..=crucible.max_run {
+                    let x = position.0.checked_add_signed(dx * run as isize).filter(|&x| x < self.width);
+                    let y = position.1.checked_add_signed(dy * run as isize).filter(|&y| y < self.height);
+                    let Some(next) = x.zip(y) else { break };
+
+                    cost += self.heat_loss[next.1 * self.width + next.0] as u32;
+                    if run < crucible.min_run {
+                        continue;
+                    }
+
+                    let next = self.node(next, axis);
+                    if cost < best[next] {
+                        best[next] = cost;
+                        previous[next] = node;
+                        queue.push(Reverse((cost, next)));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Expands the chain of runs that ends in `node` into the blocks along the way.
+    fn unwind(&self, mut node: usize, previous: &[usize]) -> Vec<Coordinate> {
+        let mut path = vec![self.block(node)];
+
+        while previous[node] != usize::MAX {
+            let (from, to) = (self.block(previous[node]), self.block(node));
+            let (dx, dy) = (from.0 as isize - to.0 as isize, from.1 as isize - to.1 as isize);
+            let steps = dx.unsigned_abs() + dy.unsigned_abs();
+
+            for step in 1..=steps as isize {
+                path.push((
+                    (to.0 as isize + dx.signum() * step) as usize,
+                    (to.1 as isize + dy.signum() * step) as usize,
+                ));
+            }
+            node = previous[node];
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Draws the city with the path on top of it, marking every block the crucible enters with
+    /// the direction it entered in.
+    pub fn render(&self, path: &[Coordinate]) -> String {
+        // `chunks` panics on a width of 0, which an empty city has.
+        let mut canvas: Vec<Vec<char>> = self.heat_loss.chunks(self.width.max(1))
+            .map(|row| row.iter().map(|&heat_loss| (b'0' + heat_loss) as char).collect())
+            .collect();
+
+        for pair in path.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            canvas[y2][x2] = match (x2.cmp(&x1), y2.cmp(&y1)) {
+                (std::cmp::Ordering::Greater, _) => '>',
+                (std::cmp::Ordering::Less, _) => '<',
+                (_, std::cmp::Ordering::Greater) => 'v',
+                _ => '^',
+            };
+        }
+
+        canvas.into_iter().fold(String::new(), |mut acc, row| {
+            acc.extend(row);
+            acc.push('\n');
+            acc
+        })
+    }
+}
+
+#[aoc_generator(day17)]
+pub fn parse(input: &str) -> Result<City, Day17Error> {
+    let Grid { tiles: heat_loss, width, height } = Grid::parse(input, |chr| chr.to_digit(10).map(|digit| digit as u8))?;
+
+    Ok(City { heat_loss, width, height })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day17::{ parse, part1 };
+/// assert_eq!(102, part1(&parse("2413432311323
+/// 3215453535623
+/// 3255245654254
+/// 3446585845452
+/// 4546657867536
+/// 1438598798454
+/// 4457876987766
+/// 3637877979653
+/// 4654967986887
+/// 4564679986453
+/// 1224686865563
+/// 2546548887735
+/// 4322674655533").unwrap()).unwrap());
+/// ```
+#[aoc(day17, part1)]
+pub fn part1(city: &City) -> Result<u32, Day17Error> {
+    city.route(Crucible::NORMAL).map(|route| route.heat_loss).ok_or(Day17Error::NoRoute)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day17::{ parse, part2 };
+/// assert_eq!(94, part2(&parse("2413432311323
+/// 3215453535623
+/// 3255245654254
+/// 3446585845452
+/// 4546657867536
+/// 1438598798454
+/// 4457876987766
+/// 3637877979653
+/// 4654967986887
+/// 4564679986453
+/// 1224686865563
+/// 2546548887735
+/// 4322674655533").unwrap()).unwrap());
+/// ```
+#[aoc(day17, part2)]
+pub fn part2(city: &City) -> Result<u32, Day17Error> {
+    city.route(Crucible::ULTRA).map(|route| route.heat_loss).ok_or(Day17Error::NoRoute)
+}
@@ -0,0 +1,213 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ bytes::complete::{ tag, take_till }, character::complete::{ anychar, char, line_ending, multispace0, space1, u64 }, multi::separated_list0, sequence::{ delimited, terminated, tuple }, Err, error::Error, IResult };
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub distance: u64,
+}
+
+/// Which part of each line of the dig plan holds the actual instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The direction and distance at the start of the line.
+    Plain,
+    /// The "color": five hexadecimal digits of distance followed by one digit of direction.
+    Hex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigPlan {
+    /// Every line of the plan, as `(plain, hex)` instructions.
+    lines: Vec<(Instruction, Instruction)>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day18Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Unknown direction {0:?} on line {1}")]
+    UnknownDirection(char, usize),
+    #[error("Malformed color #{0} on line {1}")]
+    MalformedColor(String, usize),
+    #[error("Unknown direction digit {0:?} in color on line {1}")]
+    UnknownHexDirection(char, usize),
+}
+
+impl Direction {
+    fn delta(self) -> (i64, i64) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+impl DigPlan {
+    pub fn instructions(&self, encoding: Encoding) -> impl Iterator<Item = Instruction> + '_ {
+        self.lines.iter().map(move |&(plain, hex)| match encoding {
+            Encoding::Plain => plain,
+            Encoding::Hex => hex,
+        })
+    }
+
+    /// The number of cubic meters the lagoon holds after digging out its interior.
+    ///
+    /// The shoelace formula gives the area enclosed by the centres of the trench, and by Pick's
+    /// theorem the interior holds `area - perimeter / 2 + 1` cubes. Adding the trench itself, the
+    /// total is `area + perimeter / 2 + 1`, no matter how long each instruction is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day18::{ parse, Encoding };
+    ///
+    /// let plan = parse("R 2 (#000040)
+    /// D 2 (#000041)
+    /// L 2 (#000042)
+    /// U 2 (#000043)").unwrap();
+    ///
+    /// assert_eq!(9, plan.lagoon_size(Encoding::Plain));
+    /// assert_eq!(25, plan.lagoon_size(Encoding::Hex));
+    /// ```
+    pub fn lagoon_size(&self, encoding: Encoding) -> u64 {
+        let (mut x, mut y) = (0i128, 0i128);
+        let mut twice_area = 0i128;
+        let mut perimeter = 0i128;
+
+        for Instruction { direction, distance } in self.instructions(encoding) {
+            let (dx, dy) = direction.delta();
+            let (nx, ny) = (x + (dx as i128) * distance as i128, y + (dy as i128) * distance as i128);
+
+            twice_area += x * ny - nx * y;
+            perimeter += distance as i128;
+            (x, y) = (nx, ny);
+        }
+
+        ((twice_area.abs() + perimeter) / 2 + 1) as u64
+    }
+}
+
+fn decode_color(color: &str, line: usize) -> Result<Instruction, Day18Error> {
+    let malformed = || Day18Error::MalformedColor(color.to_owned(), line);
+
+    if color.len() != 6 || !color.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(malformed());
+    }
+
+    let distance = u64::from_str_radix(&color[..5], 16).map_err(|_| malformed())?;
+    let direction = match color.as_bytes()[5] {
+        b'0' => Direction::Right,
+        b'1' => Direction::Down,
+        b'2' => Direction::Left,
+        b'3' => Direction::Up,
+        other => return Err(Day18Error::UnknownHexDirection(other as char, line)),
+    };
+
+    Ok(Instruction { direction, distance })
+}
+
+fn lines(input: &str) -> IResult<&str, Vec<(char, u64, &str)>> {
+    terminated(
+        separated_list0(line_ending, tuple((
+            terminated(anychar, space1),
+            terminated(u64, space1),
+            delimited(tag("(#"), take_till(|chr| chr == ')'), char(')')),
+        ))),
+        multispace0,
+    )(input)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day18::{ parse, Day18Error };
+///
+/// assert!(matches!(parse("X 6 (#70c710)"), Err(Day18Error::UnknownDirection('X', 0))));
+/// assert!(matches!(parse("R 6 (#70c71)"), Err(Day18Error::MalformedColor(_, 0))));
+/// assert!(matches!(parse("R 6 (#70g710)"), Err(Day18Error::MalformedColor(_, 0))));
+/// assert!(matches!(parse("R 6 (#70c714)"), Err(Day18Error::UnknownHexDirection('4', 0))));
+/// ```
+#[aoc_generator(day18)]
+pub fn parse(input: &str) -> Result<DigPlan, Day18Error> {
+    let raw = match lines(input) {
+        Ok(("", raw)) => raw,
+        Ok((rest, _)) => return Err(Day18Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day18Error::ParseError(err.to_owned())),
+    };
+
+    let lines = raw.into_iter().enumerate().map(|(line, (direction, distance, color))| {
+        let direction = match direction {
+            'U' => Direction::Up,
+            'D' => Direction::Down,
+            'L' => Direction::Left,
+            'R' => Direction::Right,
+            other => return Err(Day18Error::UnknownDirection(other, line)),
+        };
+
+        Ok((Instruction { direction, distance }, decode_color(color, line)?))
+    }).collect::<Result<_, _>>()?;
+
+    Ok(DigPlan { lines })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day18::{ parse, part1 };
+/// assert_eq!(62, part1(&parse("R 6 (#70c710)
+/// D 5 (#0dc571)
+/// L 2 (#5713f0)
+/// D 2 (#d2c081)
+/// R 2 (#59c680)
+/// D 2 (#411b91)
+/// L 5 (#8ceee2)
+/// U 2 (#caa173)
+/// L 1 (#1b58a2)
+/// U 2 (#caa171)
+/// R 2 (#7807d2)
+/// U 3 (#a77fa3)
+/// L 2 (#015232)
+/// U 2 (#7a21e3)").unwrap()));
+/// ```
+#[aoc(day18, part1)]
+pub fn part1(plan: &DigPlan) -> u64 {
+    plan.lagoon_size(Encoding::Plain)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day18::{ parse, part2 };
+/// assert_eq!(952408144115, part2(&parse("R 6 (#70c710)
+/// D 5 (#0dc571)
+/// L 2 (#5713f0)
+/// D 2 (#d2c081)
+/// R 2 (#59c680)
+/// D 2 (#411b91)
+/// L 5 (#8ceee2)
+/// U 2 (#caa173)
+/// L 1 (#1b58a2)
+/// U 2 (#caa171)
+/// R 2 (#7807d2)
+/// U 3 (#a77fa3)
+/// L 2 (#015232)
+/// U 2 (#7a21e3)").unwrap()));
+/// ```
+#[aoc(day18, part2)]
+pub fn part2(plan: &DigPlan) -> u64 {
+    plan.lagoon_size(Encoding::Hex)
+}
@@ -0,0 +1,706 @@
+use std::{ collections::HashMap, fmt::Write, ops::Range };
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ branch::alt, character::complete::{ alpha1, char, line_ending, multispace0, one_of, u32, u64 }, combinator::map, multi::{ separated_list0, separated_list1 }, sequence::{ delimited, pair, preceded, separated_pair, terminated, tuple }, Err, error::Error, IResult };
+use thiserror::Error;
+
+/// One of the four ratings of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    X = 0,
+    M = 1,
+    A = 2,
+    S = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    Less,
+    Greater,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Condition {
+    pub category: Category,
+    pub comparison: Comparison,
+    pub value: u64,
+}
+
+/// Where a part is sent once a rule matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Accept,
+    Reject,
+    /// The index of a workflow in the [`System`].
+    Workflow(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workflow {
+    pub name: String,
+    /// The conditional rules, in the order they are checked.
+    pub rules: Vec<(Condition, Target)>,
+    /// Where parts go that match none of the rules.
+    pub fallback: Target,
+}
+
+/// All workflows, with every reference between them resolved and checked to be free of cycles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct System {
+    workflows: Vec<Workflow>,
+    start: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Part {
+    pub ratings: [u64; 4],
+}
+
+/// A four dimensional box of parts, with a half-open range of ratings per category.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartRange {
+    pub ratings: [Range<u64>; 4],
+}
+
+/// A node of a [`DecisionTree`]. Branches refer to other nodes by their index in the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Node {
+    Accept,
+    Reject,
+    Branch { condition: Condition, then: usize, otherwise: usize },
+}
+
+/// The workflows flattened into a single binary decision diagram, in which every branch tests one
+/// condition that can actually go both ways, and identical subtrees are shared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTree {
+    nodes: Vec<Node>,
+    root: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub system: System,
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day19Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Workflow {0} has to end with exactly one rule without a condition")]
+    Fallback(String),
+    #[error("Workflow {0} is defined more than once")]
+    DuplicateWorkflow(String),
+    #[error("Workflow {1} refers to undefined workflow {0}")]
+    UndefinedWorkflow(String, String),
+    #[error("There is no workflow named in to start in")]
+    MissingStart,
+    #[error("Workflow {0} can send parts back to itself")]
+    Cycle(String),
+}
+
+/// A workflow as written down, with its rules as `(condition, target)` and the names of the
+/// targets not yet resolved.
+type RawWorkflow<'a> = (&'a str, Vec<(Option<Condition>, &'a str)>);
+
+/// The name of the workflow every part starts in.
+pub const START: &str = "in";
+
+/// The range every rating lies in.
+pub const RATINGS: Range<u64> = 1..4001;
+
+impl Category {
+    fn name(self) -> char {
+        match self {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        }
+    }
+}
+
+impl Condition {
+    fn label(&self) -> String {
+        let comparison = match self.comparison {
+            Comparison::Less => '<',
+            Comparison::Greater => '>',
+        };
+
+        format!("{}{}{}", self.category.name(), comparison, self.value)
+    }
+
+    pub fn matches(&self, part: &Part) -> bool {
+        let rating = part.ratings[self.category as usize];
+
+        match self.comparison {
+            Comparison::Less => rating < self.value,
+            Comparison::Greater => rating > self.value,
+        }
+    }
+
+    /// The ratings of this condition's category that match it.
+    fn matching(&self) -> Range<u64> {
+        match self.comparison {
+            Comparison::Less => 0..self.value,
+            Comparison::Greater => self.value.saturating_add(1)..u64::MAX,
+        }
+    }
+}
+
+impl Part {
+    pub fn rating(&self, category: Category) -> u64 {
+        self.ratings[category as usize]
+    }
+
+    /// The sum of all four ratings.
+    pub fn total(&self) -> u64 {
+        self.ratings.iter().sum()
+    }
+}
+
+fn intersect(a: &Range<u64>, b: &Range<u64>) -> Range<u64> {
+    a.start.max(b.start)..a.end.min(b.end)
+}
+
+impl PartRange {
+    /// The box with the same range of ratings in every category.
+    pub fn new(ratings: Range<u64>) -> Self {
+        Self { ratings: [ratings.clone(), ratings.clone(), ratings.clone(), ratings] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ratings.iter().any(Range::is_empty)
+    }
+
+    /// The smallest box that holds both `ratings` in every category and all of `parts`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day19::{ Part, PartRange, RATINGS };
+    ///
+    /// let parts = [Part { ratings: [5000, 1, 0, 2] }];
+    /// assert_eq!(PartRange { ratings: [1..5001, 1..4001, 0..4001, 1..4001] }, PartRange::covering(RATINGS, &parts));
+    /// ```
+    pub fn covering<'a>(ratings: Range<u64>, parts: impl IntoIterator<Item = &'a Part>) -> Self {
+        let mut range = Self::new(ratings);
+
+        for part in parts {
+            for (range, &rating) in range.ratings.iter_mut().zip(&part.ratings) {
+                range.start = range.start.min(rating);
+                range.end = range.end.max(rating.saturating_add(1));
+            }
+        }
+
+        range
+    }
+
+    /// The number of distinct parts in the box.
+    pub fn combinations(&self) -> u64 {
+        self.ratings.iter().map(|range| range.end.saturating_sub(range.start)).product()
+    }
+
+    /// Cuts the box in two along the plane of a condition, returning the parts that match it and
+    /// the parts that do not. Either side is `None` when it would be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day19::{ Category, Comparison, Condition, PartRange };
+    ///
+    /// let condition = Condition { category: Category::A, comparison: Comparison::Less, value: 2006 };
+    /// let (matching, rest) = PartRange::new(1..4001).split(&condition);
+    ///
+    /// assert_eq!(1..2006, matching.unwrap().ratings[2]);
+    /// assert_eq!(2006..4001, rest.unwrap().ratings[2]);
+    /// assert_eq!((None, Some(PartRange::new(2006..4001))), PartRange::new(2006..4001).split(&condition));
+    /// ```
+    pub fn split(&self, condition: &Condition) -> (Option<Self>, Option<Self>) {
+        let idx = condition.category as usize;
+        let current = &self.ratings[idx];
+        let matching = intersect(current, &condition.matching());
+        let rest = match condition.comparison {
+            Comparison::Less => intersect(current, &(condition.value..u64::MAX)),
+            Comparison::Greater => intersect(current, &(0..condition.value.saturating_add(1))),
+        };
+
+        let with = |range: Range<u64>| {
+            let mut ratings = self.ratings.clone();
+            ratings[idx] = range;
+            Some(Self { ratings }).filter(|part_range| !part_range.is_empty())
+        };
+
+        (with(matching), with(rest))
+    }
+}
+
+impl Workflow {
+    /// Where a part goes from this workflow.
+    pub fn target(&self, part: &Part) -> Target {
+        self.rules.iter()
+            .find(|(condition, _)| condition.matches(part))
+            .map_or(self.fallback, |&(_, target)| target)
+    }
+
+    fn targets(&self) -> impl Iterator<Item = Target> + '_ {
+        self.rules.iter().map(|&(_, target)| target).chain(std::iter::once(self.fallback))
+    }
+}
+
+impl System {
+    /// Resolves the names of the workflows, and makes sure that every part ends up either
+    /// accepted or rejected.
+    fn new(workflows: Vec<RawWorkflow>) -> Result<Self, Day19Error> {
+        let mut indices = HashMap::with_capacity(workflows.len());
+        for (idx, &(name, _)) in workflows.iter().enumerate() {
+            if indices.insert(name, idx).is_some() {
+                return Err(Day19Error::DuplicateWorkflow(name.to_owned()));
+            }
+        }
+
+        let start = *indices.get(START).ok_or(Day19Error::MissingStart)?;
+
+        let workflows = workflows.iter().map(|(name, rules)| {
+            let resolve = |target: &str| match target {
+                "A" => Ok(Target::Accept),
+                "R" => Ok(Target::Reject),
+                other => indices.get(other)
+                    .map(|&idx| Target::Workflow(idx))
+                    .ok_or_else(|| Day19Error::UndefinedWorkflow(other.to_owned(), name.to_string())),
+            };
+
+            let Some(((None, fallback), rules)) = rules.split_last() else {
+                return Err(Day19Error::Fallback(name.to_string()));
+            };
+
+            let rules = rules.iter().map(|&(condition, target)| {
+                let condition = condition.ok_or_else(|| Day19Error::Fallback(name.to_string()))?;
+                Ok((condition, resolve(target)?))
+            }).collect::<Result<_, _>>()?;
+
+            Ok(Workflow { name: name.to_string(), rules, fallback: resolve(fallback)? })
+        }).collect::<Result<_, _>>()?;
+
+        let system = Self { workflows, start };
+        system.check_cycles()?;
+
+        Ok(system)
+    }
+
+    /// Runs a depth first search from every workflow, failing on the first edge back into a
+    /// workflow that is still on the stack.
+    fn check_cycles(&self) -> Result<(), Day19Error> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum State { Unvisited, Active, Done }
+
+        let mut state = vec![State::Unvisited; self.workflows.len()];
+
+        for root in 0..self.workflows.len() {
+            if state[root] != State::Unvisited {
+                continue;
+            }
+
+            state[root] = State::Active;
+            let mut stack = vec![(root, self.workflows[root].targets())];
+
+            while let Some((current, targets)) = stack.last_mut() {
+                match targets.next() {
+                    Some(Target::Workflow(next)) => match state[next] {
+                        State::Active => return Err(Day19Error::Cycle(self.workflows[next].name.clone())),
+                        State::Unvisited => {
+                            state[next] = State::Active;
+                            stack.push((next, self.workflows[next].targets()));
+                        },
+                        State::Done => {},
+                    },
+                    Some(_) => {},
+                    None => {
+                        state[*current] = State::Done;
+                        stack.pop();
+                    },
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn workflows(&self) -> &[Workflow] {
+        &self.workflows
+    }
+
+    /// The workflow every part starts in.
+    pub fn start(&self) -> &Workflow {
+        &self.workflows[self.start]
+    }
+
+    pub fn workflow(&self, name: &str) -> Option<&Workflow> {
+        self.workflows.iter().find(|workflow| workflow.name == name)
+    }
+
+    /// Sends a part through the workflows, starting in `in`.
+    pub fn accepts(&self, part: &Part) -> bool {
+        let mut current = self.start;
+
+        loop {
+            match self.workflows[current].target(part) {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(next) => current = next,
+            }
+        }
+    }
+
+    /// Splits a box of parts into the boxes that end up accepted.
+    ///
+    /// Every rule cuts the box that reaches it in two: the half that matches moves on to the
+    /// rule's target, the other half is checked against the next rule.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day19::{ parse, PartRange };
+    ///
+    /// let input = parse("in{x<2001:A,R}
+    ///
+    /// {x=787,m=2655,a=1222,s=2876}").unwrap();
+    ///
+    /// assert_eq!(vec![PartRange { ratings: [1..2001, 1..4001, 1..4001, 1..4001] }], input.system.accepted(PartRange::new(1..4001)));
+    /// ```
+    pub fn accepted(&self, parts: PartRange) -> Vec<PartRange> {
+        let mut accepted = Vec::new();
+        let mut queue = vec![(Target::Workflow(self.start), parts)];
+
+        while let Some((target, parts)) = queue.pop() {
+            let workflow = match target {
+                Target::Accept => {
+                    accepted.push(parts);
+                    continue;
+                },
+                Target::Reject => continue,
+                Target::Workflow(idx) => &self.workflows[idx],
+            };
+
+            let mut rest = Some(parts);
+            for (condition, target) in &workflow.rules {
+                let Some(parts) = rest else { break };
+                let (matching, remaining) = parts.split(condition);
+
+                queue.extend(matching.map(|matching| (*target, matching)));
+                rest = remaining;
+            }
+            queue.extend(rest.map(|rest| (workflow.fallback, rest)));
+        }
+
+        accepted
+    }
+
+    /// The number of distinct parts in a box that end up accepted.
+    pub fn accepted_combinations(&self, parts: PartRange) -> u64 {
+        self.accepted(parts).iter().map(PartRange::combinations).sum()
+    }
+
+    /// Compiles the workflows into a decision tree that gives the same answers for every part in
+    /// `parts`.
+    ///
+    /// While following the rules, the box of parts that can still reach a rule is tracked just
+    /// like in [`System::accepted`]. Rules that match all or none of that box are dropped, and a
+    /// branch whose both sides lead to the same node is replaced by that node, so workflows that
+    /// always end in the same outcome disappear as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day19::{ parse, Node, PartRange, RATINGS };
+    ///
+    /// let input = parse("in{x<10:px,s>4000:A,m>5:R,R}
+    /// px{x<20:A,R}
+    ///
+    /// {x=787,m=2655,a=1222,s=2876}").unwrap();
+    ///
+    /// let tree = input.system.compile(PartRange::new(RATINGS));
+    /// assert_eq!(3, tree.nodes().len());
+    /// assert!(matches!(tree.nodes()[tree.root()], Node::Branch { .. }));
+    /// ```
+    pub fn compile(&self, parts: PartRange) -> DecisionTree {
+        let mut tree = DecisionTree { nodes: Vec::new(), root: 0 };
+        let mut interned = HashMap::new();
+
+        tree.root = self.compile_rule(Target::Workflow(self.start), 0, parts, &mut tree.nodes, &mut interned);
+        tree
+    }
+
+    /// Compiles the rules of `target` starting at `rule` for the parts in `parts`, and returns the
+    /// index of the resulting node.
+    fn compile_rule(
+        &self,
+        target: Target,
+        rule: usize,
+        parts: PartRange,
+        nodes: &mut Vec<Node>,
+        interned: &mut HashMap<Node, usize>,
+    ) -> usize {
+        let workflow = match target {
+            Target::Accept => return intern(Node::Accept, nodes, interned),
+            Target::Reject => return intern(Node::Reject, nodes, interned),
+            Target::Workflow(idx) => &self.workflows[idx],
+        };
+
+        let Some(&(condition, then)) = workflow.rules.get(rule) else {
+            return self.compile_rule(workflow.fallback, 0, parts, nodes, interned);
+        };
+
+        match parts.split(&condition) {
+            (Some(matching), None) => self.compile_rule(then, 0, matching, nodes, interned),
+            (None, Some(rest)) => self.compile_rule(target, rule + 1, rest, nodes, interned),
+            (Some(matching), Some(rest)) => {
+                let then = self.compile_rule(then, 0, matching, nodes, interned);
+                let otherwise = self.compile_rule(target, rule + 1, rest, nodes, interned);
+
+                if then == otherwise {
+                    then
+                } else {
+                    intern(Node::Branch { condition, then, otherwise }, nodes, interned)
+                }
+            },
+            // Only an empty box splits into nothing, and no part in it is ever accepted.
+            (None, None) => intern(Node::Reject, nodes, interned),
+        }
+    }
+}
+
+/// Returns the index of an equal node that is already in the tree, or adds it.
+fn intern(node: Node, nodes: &mut Vec<Node>, interned: &mut HashMap<Node, usize>) -> usize {
+    *interned.entry(node).or_insert_with(|| {
+        nodes.push(node);
+        nodes.len() - 1
+    })
+}
+
+impl DecisionTree {
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// The index of the node every part starts in.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Follows the branches for a part until it reaches an outcome.
+    ///
+    /// The tree only decides correctly for the parts in the box it was compiled for, so parts
+    /// with other ratings need a box that covers them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day19::{ parse, PartRange, RATINGS };
+    ///
+    /// let input = parse("in{x>4000:R,A}\n\n{x=5000,m=1,a=1,s=1}").unwrap();
+    /// let part = &input.parts[0];
+    /// let tree = input.system.compile(PartRange::covering(RATINGS, &input.parts));
+    ///
+    /// assert!(!input.system.accepts(part));
+    /// assert_eq!(input.system.accepts(part), tree.accepts(part));
+    /// ```
+    pub fn accepts(&self, part: &Part) -> bool {
+        let mut current = self.root;
+
+        loop {
+            match self.nodes[current] {
+                Node::Accept => return true,
+                Node::Reject => return false,
+                Node::Branch { condition, then, otherwise } => {
+                    current = if condition.matches(part) { then } else { otherwise };
+                },
+            }
+        }
+    }
+
+    /// Writes the tree as a Graphviz graph, with solid edges for conditions that hold and dashed
+    /// edges for those that do not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day19::{ parse, PartRange, RATINGS };
+    ///
+    /// let input = parse("in{x<10:px,s>4000:A,m>5:R,R}
+    /// px{x<20:A,R}
+    ///
+    /// {x=787,m=2655,a=1222,s=2876}").unwrap();
+    ///
+    /// assert_eq!(r#"digraph workflows {
+    ///     n0 [label="A", shape=box];
+    ///     n1 [label="R", shape=box];
+    ///     n2 [label="x<10"];
+    ///     n2 -> n0;
+    ///     n2 -> n1 [style=dashed];
+    /// }
+    /// "#, input.system.compile(PartRange::new(RATINGS)).to_dot());
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph workflows {\n");
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let _ = match node {
+                Node::Accept => writeln!(dot, "    n{idx} [label=\"A\", shape=box];"),
+                Node::Reject => writeln!(dot, "    n{idx} [label=\"R\", shape=box];"),
+                Node::Branch { condition, then, otherwise } => {
+                    writeln!(dot, "    n{idx} [label=\"{}\"];", condition.label())
+                        .and_then(|_| writeln!(dot, "    n{idx} -> n{then};"))
+                        .and_then(|_| writeln!(dot, "    n{idx} -> n{otherwise} [style=dashed];"))
+                },
+            };
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn condition(input: &str) -> IResult<&str, Condition> {
+    map(
+        tuple((
+            map(one_of("xmas"), |chr| match chr {
+                'x' => Category::X,
+                'm' => Category::M,
+                'a' => Category::A,
+                _ => Category::S,
+            }),
+            map(one_of("<>"), |chr| if chr == '<' { Comparison::Less } else { Comparison::Greater }),
+            u64,
+        )),
+        |(category, comparison, value)| Condition { category, comparison, value },
+    )(input)
+}
+
+fn rule(input: &str) -> IResult<&str, (Option<Condition>, &str)> {
+    alt((
+        map(separated_pair(condition, char(':'), alpha1), |(condition, target)| (Some(condition), target)),
+        map(alpha1, |target| (None, target)),
+    ))(input)
+}
+
+fn workflow(input: &str) -> IResult<&str, RawWorkflow<'_>> {
+    pair(alpha1, delimited(char('{'), separated_list1(char(','), rule), char('}')))(input)
+}
+
+fn part(input: &str) -> IResult<&str, Part> {
+    map(
+        delimited(
+            char('{'),
+            tuple((
+                preceded(tag_rating('x'), u32),
+                preceded(pair(char(','), tag_rating('m')), u32),
+                preceded(pair(char(','), tag_rating('a')), u32),
+                preceded(pair(char(','), tag_rating('s')), u32),
+            )),
+            char('}'),
+        ),
+        |(x, m, a, s)| Part { ratings: [x, m, a, s].map(u64::from) },
+    )(input)
+}
+
+fn tag_rating<'a>(category: char) -> impl FnMut(&'a str) -> IResult<&'a str, (char, char)> {
+    pair(char(category), char('='))
+}
+
+fn sections(input: &str) -> IResult<&str, (Vec<RawWorkflow<'_>>, Vec<Part>)> {
+    terminated(
+        separated_pair(
+            separated_list1(line_ending, workflow),
+            pair(line_ending, line_ending),
+            separated_list0(line_ending, part),
+        ),
+        multispace0,
+    )(input)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day19::{ parse, Day19Error };
+///
+/// assert!(matches!(parse("in{x<10:A}\n\n"), Err(Day19Error::Fallback(name)) if name == "in"));
+/// assert!(matches!(parse("in{x<10:A,R}\nin{A}\n\n"), Err(Day19Error::DuplicateWorkflow(name)) if name == "in"));
+/// assert!(matches!(parse("in{x<10:px,R}\n\n"), Err(Day19Error::UndefinedWorkflow(name, _)) if name == "px"));
+/// assert!(matches!(parse("px{A}\n\n"), Err(Day19Error::MissingStart)));
+/// assert!(matches!(parse("in{x<10:px,R}\npx{m>5:qq,A}\nqq{in}\n\n"), Err(Day19Error::Cycle(_))));
+/// ```
+#[aoc_generator(day19)]
+pub fn parse(input: &str) -> Result<Input, Day19Error> {
+    let (workflows, parts) = match sections(input) {
+        Ok(("", sections)) => sections,
+        Ok((rest, _)) => return Err(Day19Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day19Error::ParseError(err.to_owned())),
+    };
+
+    Ok(Input { system: System::new(workflows)?, parts })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day19::{ parse, part1 };
+/// assert_eq!(19114, part1(&parse("px{a<2006:qkq,m>2090:A,rfg}
+/// pv{a>1716:R,A}
+/// lnx{m>1548:A,A}
+/// rfg{s<537:gd,x>2440:R,A}
+/// qs{s>3448:A,lnx}
+/// qkq{x<1416:A,crn}
+/// crn{x>2662:A,R}
+/// in{s<1351:px,qqz}
+/// qqz{s>2770:qs,m<1801:hdj,R}
+/// gd{a>3333:R,R}
+/// hdj{m>838:A,pv}
+///
+/// {x=787,m=2655,a=1222,s=2876}
+/// {x=1679,m=44,a=2067,s=496}
+/// {x=2036,m=264,a=79,s=2244}
+/// {x=2461,m=1339,a=466,s=291}
+/// {x=2127,m=1623,a=2188,s=1013}").unwrap()));
+/// assert_eq!(0, part1(&parse("in{x>4000:R,A}\n\n{x=5000,m=1,a=1,s=1}").unwrap()));
+/// ```
+#[aoc(day19, part1)]
+pub fn part1(input: &Input) -> u64 {
+    // Parts are not limited to the ratings of part 2, so the tree has to decide for all of them.
+    let tree = input.system.compile(PartRange::covering(RATINGS, &input.parts));
+
+    input.parts.iter()
+        .filter(|part| tree.accepts(part))
+        .map(Part::total)
+        .sum()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day19::{ parse, part2 };
+/// assert_eq!(167409079868000, part2(&parse("px{a<2006:qkq,m>2090:A,rfg}
+/// pv{a>1716:R,A}
+/// lnx{m>1548:A,A}
+/// rfg{s<537:gd,x>2440:R,A}
+/// qs{s>3448:A,lnx}
+/// qkq{x<1416:A,crn}
+/// crn{x>2662:A,R}
+/// in{s<1351:px,qqz}
+/// qqz{s>2770:qs,m<1801:hdj,R}
+/// gd{a>3333:R,R}
+/// hdj{m>838:A,pv}
+///
+/// {x=787,m=2655,a=1222,s=2876}
+/// {x=1679,m=44,a=2067,s=496}
+/// {x=2036,m=264,a=79,s=2244}
+/// {x=2461,m=1339,a=466,s=291}
+/// {x=2127,m=1623,a=2188,s=1013}").unwrap()));
+/// ```
+#[aoc(day19, part2)]
+pub fn part2(input: &Input) -> u64 {
+    input.system.accepted_combinations(PartRange::new(RATINGS))
+}
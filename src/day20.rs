@@ -0,0 +1,550 @@
+use std::{ collections::{ HashMap, VecDeque }, fmt::Write };
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ branch::alt, bytes::complete::tag, character::complete::{ alpha1, char, line_ending, multispace0 }, combinator::map, multi::separated_list1, sequence::{ preceded, separated_pair, terminated }, Err, error::Error, IResult };
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// A module that is only ever sent pulses, like `output` or `rx`.
+    Output,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    pub kind: Kind,
+    /// The indices of the modules sending pulses to this one, in the order they appear.
+    pub inputs: Vec<usize>,
+    /// The indices of the modules this one sends pulses to, in the order they are sent.
+    pub outputs: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Circuit {
+    modules: Vec<Module>,
+    broadcaster: usize,
+}
+
+/// What a module remembers between pulses.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum State {
+    Broadcaster,
+    /// Whether the flip-flop is on.
+    FlipFlop(bool),
+    /// The last pulse received from each input, in the order of [`Module::inputs`].
+    Conjunction(Vec<Pulse>),
+    /// The pulses received so far.
+    Output(Pulses),
+}
+
+/// A number of low and high pulses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Pulses {
+    pub low: u64,
+    pub high: u64,
+}
+
+/// A single pulse on its way between two modules. Pulses sent by the button come from `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signal {
+    pub from: Option<usize>,
+    pub to: usize,
+    pub pulse: Pulse,
+}
+
+/// Runs a circuit one button press at a time.
+#[derive(Debug, Clone)]
+pub struct Simulator<'a> {
+    circuit: &'a Circuit,
+    states: Vec<State>,
+    presses: u64,
+    pulses: Pulses,
+}
+
+/// A chain of flip-flops that counts button presses in binary, together with the conjunction
+/// that resets it once it reaches its period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter {
+    /// The flip-flops of the chain, least significant bit first.
+    pub flip_flops: Vec<usize>,
+    /// The conjunction that watches the bits of the period and resets the chain.
+    pub hub: usize,
+    /// The conjunction that turns the low pulse of the hub into a high pulse for the feed.
+    pub inverter: usize,
+    /// The number of presses after which the counter wraps around, sending a high pulse.
+    pub period: u64,
+}
+
+/// The structure of a circuit in which a single conjunction combines several binary counters
+/// into the pulse for an output module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// The conjunction feeding the output.
+    pub feed: usize,
+    pub counters: Vec<Counter>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day20Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Module {0} is defined more than once")]
+    DuplicateModule(String),
+    #[error("There is no broadcaster")]
+    MissingBroadcaster,
+    #[error("There is no module named {0}")]
+    MissingOutput(String),
+    #[error("Module {0} is not fed by a single conjunction")]
+    UnexpectedFeed(String),
+    #[error("Module {0} is not the end of a binary counter")]
+    NotACounter(String),
+}
+
+/// A module as written down: its kind, its name and the names of its outputs.
+type RawModule<'a> = ((Kind, &'a str), Vec<&'a str>);
+
+const BROADCASTER: &str = "broadcaster";
+
+/// The module that has to receive a low pulse in part 2.
+pub const RX: &str = "rx";
+
+impl Pulses {
+    fn count(&mut self, pulse: Pulse) {
+        match pulse {
+            Pulse::Low => self.low += 1,
+            Pulse::High => self.high += 1,
+        }
+    }
+}
+
+impl Circuit {
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// The index of the module with the given name.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|module| module.name == name)
+    }
+
+    /// The index of the module the button sends its pulse to.
+    pub fn broadcaster(&self) -> usize {
+        self.broadcaster
+    }
+
+    /// The state of every module before the button is pressed for the first time.
+    pub fn initial_states(&self) -> Vec<State> {
+        self.modules.iter().map(|module| match module.kind {
+            Kind::Broadcaster => State::Broadcaster,
+            Kind::FlipFlop => State::FlipFlop(false),
+            Kind::Conjunction => State::Conjunction(vec![Pulse::Low; module.inputs.len()]),
+            Kind::Output => State::Output(Pulses::default()),
+        }).collect()
+    }
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(circuit: &'a Circuit) -> Self {
+        Self { circuit, states: circuit.initial_states(), presses: 0, pulses: Pulses::default() }
+    }
+
+    /// How often the button has been pressed.
+    pub fn presses(&self) -> u64 {
+        self.presses
+    }
+
+    /// The pulses sent over all presses so far, including those sent by the button.
+    pub fn pulses(&self) -> Pulses {
+        self.pulses
+    }
+
+    pub fn states(&self) -> &[State] {
+        &self.states
+    }
+
+    pub fn state(&self, module: usize) -> &State {
+        &self.states[module]
+    }
+
+    /// Puts every module back into its initial state and forgets all presses.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.circuit);
+    }
+
+    /// Presses the button once, and returns the pulses sent until the circuit settles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day20::{ parse, Pulses, Simulator, State };
+    ///
+    /// let circuit = parse("broadcaster -> a
+    /// %a -> inv, con
+    /// &inv -> b
+    /// %b -> con
+    /// &con -> output").unwrap();
+    /// let mut simulator = Simulator::new(&circuit);
+    ///
+    /// assert_eq!(Pulses { low: 4, high: 4 }, simulator.press());
+    /// assert_eq!(&State::FlipFlop(true), simulator.state(circuit.index("a").unwrap()));
+    /// assert_eq!(Pulses { low: 4, high: 2 }, simulator.press());
+    /// assert_eq!(&State::FlipFlop(false), simulator.state(circuit.index("a").unwrap()));
+    /// assert_eq!(&State::Output(Pulses { low: 1, high: 2 }), simulator.state(circuit.index("output").unwrap()));
+    /// ```
+    pub fn press(&mut self) -> Pulses {
+        self.press_with(|_| {})
+    }
+
+    /// Presses the button once like [`Simulator::press`], handing every pulse to `observer` in the
+    /// order the pulses are processed.
+    pub fn press_with(&mut self, mut observer: impl FnMut(Signal)) -> Pulses {
+        let mut pulses = Pulses::default();
+        let mut queue = VecDeque::from([Signal { from: None, to: self.circuit.broadcaster, pulse: Pulse::Low }]);
+
+        while let Some(signal) = queue.pop_front() {
+            observer(signal);
+            pulses.count(signal.pulse);
+
+            let module = &self.circuit.modules[signal.to];
+            let sent = match &mut self.states[signal.to] {
+                State::Broadcaster => Some(signal.pulse),
+                State::FlipFlop(on) => match signal.pulse {
+                    Pulse::High => None,
+                    Pulse::Low => {
+                        *on = !*on;
+                        Some(if *on { Pulse::High } else { Pulse::Low })
+                    },
+                },
+                State::Conjunction(memory) => {
+                    if let Some(slot) = signal.from.and_then(|from| module.inputs.iter().position(|&input| input == from)) {
+                        memory[slot] = signal.pulse;
+                    }
+                    Some(if memory.iter().all(|&pulse| pulse == Pulse::High) { Pulse::Low } else { Pulse::High })
+                },
+                State::Output(received) => {
+                    received.count(signal.pulse);
+                    None
+                },
+            };
+
+            if let Some(pulse) = sent {
+                queue.extend(module.outputs.iter().map(|&to| Signal { from: Some(signal.to), to, pulse }));
+            }
+        }
+
+        self.presses += 1;
+        self.pulses.low += pulses.low;
+        self.pulses.high += pulses.high;
+
+        pulses
+    }
+}
+
+impl Circuit {
+    /// Breaks down the modules feeding `output` into independent binary counters.
+    ///
+    /// The output has to be fed by a single conjunction, each input of which is an inverter behind
+    /// the hub of a counter. The hub listens to the flip-flops of the chain that are set in the
+    /// period, and sends pulses to the others and to the first one, so once the count reaches the
+    /// period, the hub fires and the whole chain wraps around to zero in the same press. Reading
+    /// the period is then just a matter of reading which flip-flops feed the hub.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day20::{ parse, Day20Error };
+    ///
+    /// let circuit = parse("broadcaster -> ax, bx
+    /// %ax -> ay, ha
+    /// %ay -> az
+    /// %az -> ha
+    /// &ha -> ax, ay, ia
+    /// &ia -> feed
+    /// %bx -> by, hb
+    /// %by -> hb
+    /// &hb -> bx, ib
+    /// &ib -> feed
+    /// &feed -> rx").unwrap();
+    ///
+    /// let analysis = circuit.analyze("rx").unwrap();
+    /// let periods: Vec<_> = analysis.counters.iter().map(|counter| counter.period).collect();
+    /// assert_eq!(vec![5, 3], periods);
+    /// assert_eq!(15, analysis.first_pulse());
+    ///
+    /// assert!(matches!(circuit.analyze("nowhere"), Err(Day20Error::MissingOutput(_))));
+    /// assert!(matches!(circuit.analyze("ax"), Err(Day20Error::UnexpectedFeed(_))));
+    /// assert!(matches!(circuit.analyze("ia"), Err(Day20Error::NotACounter(_))));
+    /// ```
+    pub fn analyze(&self, output: &str) -> Result<Analysis, Day20Error> {
+        let output = self.index(output).ok_or_else(|| Day20Error::MissingOutput(output.to_owned()))?;
+
+        let feed = match self.modules[output].inputs[..] {
+            [feed] if self.modules[feed].kind == Kind::Conjunction => feed,
+            _ => return Err(Day20Error::UnexpectedFeed(self.modules[output].name.clone())),
+        };
+
+        let counters = self.modules[feed].inputs.iter()
+            .map(|&inverter| self.counter(inverter).ok_or_else(|| Day20Error::NotACounter(self.modules[inverter].name.clone())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Analysis { feed, counters })
+    }
+
+    /// Follows the counter behind an inverter back to its flip-flops.
+    fn counter(&self, inverter: usize) -> Option<Counter> {
+        let is = |module: usize, kind: Kind| self.modules[module].kind == kind;
+
+        let hub = match self.modules[inverter].inputs[..] {
+            [hub] if is(inverter, Kind::Conjunction) && is(hub, Kind::Conjunction) => hub,
+            _ => return None,
+        };
+
+        let mut current = *self.modules[self.broadcaster].outputs.iter()
+            .find(|&&head| is(head, Kind::FlipFlop) && self.modules[head].outputs.contains(&hub))?;
+        let mut flip_flops = Vec::new();
+        let mut period = 0u64;
+
+        loop {
+            let module = &self.modules[current];
+            let feeds = module.outputs.contains(&hub);
+            let fed = self.modules[hub].outputs.contains(&current);
+
+            // The first bit always feeds and is fed by the hub, every other bit does just one.
+            let wired = if flip_flops.is_empty() { feeds && fed } else { feeds != fed };
+            if !wired || flip_flops.len() >= u64::BITS as usize - 1 || flip_flops.contains(&current) {
+                return None;
+            }
+
+            if feeds {
+                period |= 1 << flip_flops.len();
+            }
+            flip_flops.push(current);
+
+            let mut next = module.outputs.iter().filter(|&&next| next != hub);
+            match (next.next(), next.next()) {
+                (Some(&next), None) if is(next, Kind::FlipFlop) => current = next,
+                (None, None) => break,
+                _ => return None,
+            }
+        }
+
+        let hub_inputs_ok = self.modules[hub].inputs.iter().all(|input| flip_flops.contains(input));
+        let hub_outputs_ok = self.modules[hub].outputs.iter().all(|&output| output == inverter || flip_flops.contains(&output));
+
+        (hub_inputs_ok && hub_outputs_ok).then_some(Counter { flip_flops, hub, inverter, period })
+    }
+
+    /// Writes the circuit as a Graphviz graph, drawing every counter in a box of its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day20::parse;
+    ///
+    /// let circuit = parse("broadcaster -> ax
+    /// %ax -> ha
+    /// &ha -> ax, ia
+    /// &ia -> feed
+    /// &feed -> rx").unwrap();
+    ///
+    /// let analysis = circuit.analyze("rx").unwrap();
+    /// assert_eq!(r#"digraph circuit {
+    ///     subgraph cluster_0 {
+    ///         label="period 1";
+    ///         style=filled;
+    ///         fillcolor=lightgrey;
+    ///         "ax" [shape=box];
+    ///         "ha" [shape=ellipse];
+    ///         "ia" [shape=ellipse];
+    ///     }
+    ///     "broadcaster" [shape=house];
+    ///     "feed" [shape=ellipse];
+    ///     "rx" [shape=doublecircle];
+    ///     "broadcaster" -> "ax";
+    ///     "ax" -> "ha";
+    ///     "ha" -> "ax";
+    ///     "ha" -> "ia";
+    ///     "ia" -> "feed";
+    ///     "feed" -> "rx";
+    /// }
+    /// "#, circuit.to_dot(&analysis.counters));
+    /// ```
+    pub fn to_dot(&self, counters: &[Counter]) -> String {
+        let node = |module: &Module| {
+            let shape = match module.kind {
+                Kind::Broadcaster => "house",
+                Kind::FlipFlop => "box",
+                Kind::Conjunction => "ellipse",
+                Kind::Output => "doublecircle",
+            };
+            format!("\"{}\" [shape={}];", module.name, shape)
+        };
+
+        let mut dot = String::from("digraph circuit {\n");
+        let mut clustered = vec![false; self.modules.len()];
+
+        for (idx, counter) in counters.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{idx} {{");
+            let _ = writeln!(dot, "        label=\"period {}\";", counter.period);
+            dot.push_str("        style=filled;\n        fillcolor=lightgrey;\n");
+
+            for &module in counter.flip_flops.iter().chain([&counter.hub, &counter.inverter]) {
+                clustered[module] = true;
+                let _ = writeln!(dot, "        {}", node(&self.modules[module]));
+            }
+            dot.push_str("    }\n");
+        }
+
+        for (module, _) in self.modules.iter().zip(&clustered).filter(|(_, &clustered)| !clustered) {
+            let _ = writeln!(dot, "    {}", node(module));
+        }
+
+        for module in &self.modules {
+            for &output in &module.outputs {
+                let _ = writeln!(dot, "    \"{}\" -> \"{}\";", module.name, self.modules[output].name);
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Analysis {
+    /// The first press in which every counter wraps around at once, so that the feed sends a
+    /// low pulse to the output.
+    pub fn first_pulse(&self) -> u64 {
+        self.counters.iter().map(|counter| counter.period).fold(1, num::integer::lcm)
+    }
+}
+
+fn module(input: &str) -> IResult<&str, RawModule<'_>> {
+    separated_pair(
+        alt((
+            map(tag(BROADCASTER), |name| (Kind::Broadcaster, name)),
+            map(preceded(char('%'), alpha1), |name| (Kind::FlipFlop, name)),
+            map(preceded(char('&'), alpha1), |name| (Kind::Conjunction, name)),
+        )),
+        tag(" -> "),
+        separated_list1(tag(", "), alpha1),
+    )(input)
+}
+
+fn modules(input: &str) -> IResult<&str, Vec<RawModule<'_>>> {
+    terminated(separated_list1(line_ending, module), multispace0)(input)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day20::{ parse, Day20Error, Kind };
+///
+/// let circuit = parse("broadcaster -> a\n%a -> rx").unwrap();
+/// assert_eq!(Kind::Output, circuit.modules()[circuit.index("rx").unwrap()].kind);
+///
+/// assert!(matches!(parse("broadcaster -> a\n%a -> b\n&a -> b"), Err(Day20Error::DuplicateModule(name)) if name == "a"));
+/// assert!(matches!(parse("%a -> b"), Err(Day20Error::MissingBroadcaster)));
+/// ```
+#[aoc_generator(day20)]
+pub fn parse(input: &str) -> Result<Circuit, Day20Error> {
+    let raw = match modules(input) {
+        Ok(("", raw)) => raw,
+        Ok((rest, _)) => return Err(Day20Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day20Error::ParseError(err.to_owned())),
+    };
+
+    let mut indices = HashMap::with_capacity(raw.len());
+    let mut modules = Vec::with_capacity(raw.len());
+    for &((kind, name), _) in &raw {
+        if indices.insert(name, modules.len()).is_some() {
+            return Err(Day20Error::DuplicateModule(name.to_owned()));
+        }
+        modules.push(Module { name: name.to_owned(), kind, inputs: Vec::new(), outputs: Vec::new() });
+    }
+
+    for (from, (_, outputs)) in raw.into_iter().enumerate() {
+        for name in outputs {
+            let to = *indices.entry(name).or_insert_with(|| {
+                modules.push(Module { name: name.to_owned(), kind: Kind::Output, inputs: Vec::new(), outputs: Vec::new() });
+                modules.len() - 1
+            });
+
+            modules[from].outputs.push(to);
+            modules[to].inputs.push(from);
+        }
+    }
+
+    let broadcaster = *indices.get(BROADCASTER).ok_or(Day20Error::MissingBroadcaster)?;
+
+    Ok(Circuit { modules, broadcaster })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day20::{ parse, part1 };
+/// assert_eq!(32000000, part1(&parse("broadcaster -> a, b, c
+/// %a -> b
+/// %b -> c
+/// %c -> inv
+/// &inv -> a").unwrap()));
+/// assert_eq!(11687500, part1(&parse("broadcaster -> a
+/// %a -> inv, con
+/// &inv -> b
+/// %b -> con
+/// &con -> output").unwrap()));
+/// ```
+#[aoc(day20, part1)]
+pub fn part1(circuit: &Circuit) -> u64 {
+    let mut simulator = Simulator::new(circuit);
+    for _ in 0..1000 {
+        simulator.press();
+    }
+
+    let Pulses { low, high } = simulator.pulses();
+    low * high
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day20::{ parse, part2, Pulse, Simulator };
+///
+/// let circuit = parse("broadcaster -> ax, bx
+/// %ax -> ay, ha
+/// %ay -> az
+/// %az -> ha
+/// &ha -> ax, ay, ia
+/// &ia -> feed
+/// %bx -> by, hb
+/// %by -> hb
+/// &hb -> bx, ib
+/// &ib -> feed
+/// &feed -> rx").unwrap();
+///
+/// let rx = circuit.index("rx").unwrap();
+/// let mut simulator = Simulator::new(&circuit);
+/// let mut fired = false;
+/// while !fired {
+///     simulator.press_with(|signal| fired |= signal.to == rx && signal.pulse == Pulse::Low);
+/// }
+///
+/// assert_eq!(15, simulator.presses());
+/// assert_eq!(15, part2(&circuit).unwrap());
+/// ```
+#[aoc(day20, part2)]
+pub fn part2(circuit: &Circuit) -> Result<u64, Day20Error> {
+    circuit.analyze(RX).map(|analysis| analysis.first_pulse())
+}
@@ -0,0 +1,255 @@
+use std::collections::VecDeque;
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+use crate::grid::{ Grid, GridError };
+
+type Coordinate = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Garden {
+    rocks: Vec<bool>,
+    width: usize,
+    height: usize,
+    start: Coordinate,
+}
+
+#[derive(Debug, Error)]
+pub enum Day21Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("There is no starting position")]
+    MissingStart,
+    #[error("There is a second starting position at {0:?}")]
+    MultipleStarts(Coordinate),
+    #[error("The garden is {0}x{1} tiles, but has to be square to be tiled evenly")]
+    NotSquare(usize, usize),
+    #[error("The starting position {0:?} is not in the centre of the garden")]
+    OffCentre(Coordinate),
+    #[error("There is a rock at {0:?} on the row or column through the starting position")]
+    Blocked(Coordinate),
+}
+
+/// The number of steps the elf takes in part 1.
+pub const STEPS: u32 = 64;
+
+/// The number of steps the elf takes in part 2.
+pub const TILED_STEPS: u64 = 26_501_365;
+
+impl Garden {
+    fn is_rock(&self, (x, y): Coordinate) -> bool {
+        self.rocks[y * self.width + x]
+    }
+
+    /// The number of steps to each plot from the starting position, or `None` for rocks and
+    /// plots that cannot be reached.
+    pub fn distances(&self) -> Vec<Option<u32>> {
+        let mut distances = vec![None; self.rocks.len()];
+        let mut queue = VecDeque::from([(self.start, 0)]);
+        distances[self.start.1 * self.width + self.start.0] = Some(0);
+
+        while let Some(((x, y), distance)) = queue.pop_front() {
+            let neighbours = [
+                x.checked_sub(1).map(|x| (x, y)),
+                (x + 1 < self.width).then_some((x + 1, y)),
+                y.checked_sub(1).map(|y| (x, y)),
+                (y + 1 < self.height).then_some((x, y + 1)),
+            ];
+
+            for next in neighbours.into_iter().flatten() {
+                let idx = next.1 * self.width + next.0;
+                if !self.is_rock(next) && distances[idx].is_none() {
+                    distances[idx] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The number of plots the elf can end up on after exactly `steps` steps.
+    ///
+    /// Since the elf can always step back and forth, those are exactly the plots that can be
+    /// reached in at most `steps` steps, and whose distance has the same parity as `steps`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day21::parse;
+    ///
+    /// let garden = parse("...........
+    /// .....###.#.
+    /// .###.##..#.
+    /// ..#.#...#..
+    /// ....#.#....
+    /// .##..S####.
+    /// .##..#...#.
+    /// .......##..
+    /// .##.#.####.
+    /// .##..##.##.
+    /// ...........").unwrap();
+    ///
+    /// assert_eq!(2, garden.reachable(1));
+    /// assert_eq!(16, garden.reachable(6));
+    /// ```
+    pub fn reachable(&self, steps: u32) -> usize {
+        self.distances().into_iter()
+            .flatten()
+            .filter(|&distance| distance <= steps && distance % 2 == steps % 2)
+            .count()
+    }
+
+    /// The number of plots the elf can end up on after exactly `steps` steps when the garden
+    /// repeats infinitely in every direction, found by a breadth first search over the tiles.
+    ///
+    /// This visits every plot within `steps` of the start, so it is only suitable for small
+    /// numbers of steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day21::parse;
+    ///
+    /// let garden = parse("...........
+    /// .....###.#.
+    /// .###.##..#.
+    /// ..#.#...#..
+    /// ....#.#....
+    /// .##..S####.
+    /// .##..#...#.
+    /// .......##..
+    /// .##.#.####.
+    /// .##..##.##.
+    /// ...........").unwrap();
+    ///
+    /// assert_eq!(16, garden.reachable_unbounded(6));
+    /// assert_eq!(50, garden.reachable_unbounded(10));
+    /// assert_eq!(1594, garden.reachable_unbounded(50));
+    /// assert_eq!(6536, garden.reachable_unbounded(100));
+    /// ```
+    pub fn reachable_unbounded(&self, steps: u32) -> u64 {
+        let radius = steps as i64;
+        let side = 2 * radius as usize + 1;
+        let mut seen = vec![false; side * side];
+        let mut queue = VecDeque::from([((0i64, 0i64), 0u32)]);
+        let mut count = 0;
+        seen[radius as usize * side + radius as usize] = true;
+
+        while let Some(((dx, dy), distance)) = queue.pop_front() {
+            if distance % 2 == steps % 2 {
+                count += 1;
+            }
+            if distance == steps {
+                continue;
+            }
+
+            for (nx, ny) in [(dx - 1, dy), (dx + 1, dy), (dx, dy - 1), (dx, dy + 1)] {
+                let x = (self.start.0 as i64 + nx).rem_euclid(self.width as i64) as usize;
+                let y = (self.start.1 as i64 + ny).rem_euclid(self.height as i64) as usize;
+                let idx = (ny + radius) as usize * side + (nx + radius) as usize;
+
+                if !self.is_rock((x, y)) && !seen[idx] {
+                    seen[idx] = true;
+                    queue.push_back(((nx, ny), distance + 1));
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Makes sure that the garden tiles the plane in a way that lets the number of reachable
+    /// plots grow quadratically with every garden's width of steps.
+    ///
+    /// This needs a square garden with the start in its centre, and free paths along the row and
+    /// the column through the start, so that the elf reaches every copy of the garden the same
+    /// number of steps after the previous one along each axis.
+    pub fn check_tiling(&self) -> Result<(), Day21Error> {
+        if self.width != self.height {
+            return Err(Day21Error::NotSquare(self.width, self.height));
+        }
+        if self.start != (self.width / 2, self.height / 2) {
+            return Err(Day21Error::OffCentre(self.start));
+        }
+
+        let row = (0..self.width).map(|x| (x, self.start.1));
+        let column = (0..self.height).map(|y| (self.start.0, y));
+        match row.chain(column).find(|&tile| self.is_rock(tile)) {
+            Some(rock) => Err(Day21Error::Blocked(rock)),
+            None => Ok(()),
+        }
+    }
+
+    /// The number of plots the elf can end up on after exactly `steps` steps when the garden
+    /// repeats infinitely in every direction.
+    ///
+    /// With `steps = k * width + r`, the number of reachable plots is a quadratic polynomial in
+    /// `k`, so it is fitted through the counts for `k` = 0, 1 and 2 from
+    /// [`Garden::reachable_unbounded`] and then evaluated at the actual `k`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day21::{ parse, Day21Error };
+    ///
+    /// let garden = parse(".....
+    /// .#.#.
+    /// ..S..
+    /// .#.#.
+    /// .....").unwrap();
+    ///
+    /// assert_eq!(garden.reachable_unbounded(57), garden.reachable_tiled(57).unwrap());
+    /// assert_eq!(garden.reachable_unbounded(60), garden.reachable_tiled(60).unwrap());
+    ///
+    /// let blocked = parse("...\n.S#\n...").unwrap();
+    /// assert!(matches!(blocked.reachable_tiled(100), Err(Day21Error::Blocked((2, 1)))));
+    /// ```
+    pub fn reachable_tiled(&self, steps: u64) -> Result<u64, Day21Error> {
+        self.check_tiling()?;
+
+        let width = self.width as u64;
+        let (k, remainder) = (steps / width, steps % width);
+        let samples: Vec<i64> = (0..3)
+            .map(|n| self.reachable_unbounded((remainder + n * width) as u32) as i64)
+            .collect();
+
+        if k < 3 {
+            return Ok(samples[k as usize] as u64);
+        }
+
+        let (first, second) = (samples[1] - samples[0], samples[2] - 2 * samples[1] + samples[0]);
+        let k = k as i64;
+
+        Ok((samples[0] + k * first + k * (k - 1) / 2 * second) as u64)
+    }
+}
+
+#[aoc_generator(day21)]
+pub fn parse(input: &str) -> Result<Garden, Day21Error> {
+    let Grid { tiles, width, height } = Grid::parse(input, |chr| matches!(chr, '.' | '#' | 'S').then_some(chr))?;
+
+    let mut starts = tiles.iter()
+        .enumerate()
+        .filter(|&(_, &tile)| tile == 'S')
+        .map(|(idx, _)| (idx % width, idx / width));
+    let start = starts.next().ok_or(Day21Error::MissingStart)?;
+    if let Some(second) = starts.next() {
+        return Err(Day21Error::MultipleStarts(second));
+    }
+
+    let rocks = tiles.iter().map(|&tile| tile == '#').collect();
+
+    Ok(Garden { rocks, width, height, start })
+}
+
+#[aoc(day21, part1)]
+pub fn part1(garden: &Garden) -> usize {
+    garden.reachable(STEPS)
+}
+
+#[aoc(day21, part2)]
+pub fn part2(garden: &Garden) -> Result<u64, Day21Error> {
+    garden.reachable_tiled(TILED_STEPS)
+}
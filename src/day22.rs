@@ -0,0 +1,287 @@
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ character::complete::{ char, line_ending, multispace0, u32 }, combinator::map, multi::separated_list0, sequence::{ separated_pair, terminated, tuple }, Err, error::Error, IResult };
+use thiserror::Error;
+
+type Coordinate = (u32, u32, u32);
+
+/// A brick, spanning the cubes from `start` to `end` inclusive, with `start` being the corner
+/// closest to the ground.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick {
+    pub start: Coordinate,
+    pub end: Coordinate,
+}
+
+/// The bricks after they have all fallen down, together with which bricks rest on which.
+///
+/// Bricks are referred to by their position in the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack {
+    bricks: Vec<Brick>,
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+    /// The immediate dominator of every brick, where `None` stands for the ground: the last
+    /// brick that every chain of supports from the ground up to the brick has to pass through.
+    dominators: Vec<Option<usize>>,
+    /// The number of bricks every brick dominates, not counting itself.
+    dominated: Vec<usize>,
+}
+
+#[derive(Debug, Error)]
+pub enum Day22Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("Brick {0} extends in more than one direction")]
+    NotStraight(usize),
+    #[error("Brick {0} is not above the ground")]
+    Underground(usize),
+}
+
+impl Brick {
+    fn footprint(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (self.start.0..=self.end.0).flat_map(move |x| (self.start.1..=self.end.1).map(move |y| (x, y)))
+    }
+
+    fn height(&self) -> u32 {
+        self.end.2 - self.start.2 + 1
+    }
+}
+
+impl Stack {
+    /// Lets the bricks fall until they come to rest.
+    ///
+    /// Bricks are dropped from the lowest up, and a height map of the top of the stack tells for
+    /// every column where a brick lands and on which bricks it lands.
+    pub fn settle(bricks: &[Brick]) -> Self {
+        let width = bricks.iter().map(|brick| brick.end.0 as usize + 1).max().unwrap_or(0);
+        let depth = bricks.iter().map(|brick| brick.end.1 as usize + 1).max().unwrap_or(0);
+        let mut top: Vec<(u32, Option<usize>)> = vec![(0, None); width * depth];
+
+        let mut order: Vec<usize> = (0..bricks.len()).collect();
+        order.sort_by_key(|&idx| bricks[idx].start.2);
+
+        let mut settled = bricks.to_vec();
+        let mut supported_by = vec![Vec::new(); bricks.len()];
+        let mut supports = vec![Vec::new(); bricks.len()];
+
+        for &idx in &order {
+            let brick = bricks[idx];
+            let columns: Vec<usize> = brick.footprint().map(|(x, y)| y as usize * width + x as usize).collect();
+            let floor = columns.iter().map(|&column| top[column].0).max().unwrap_or(0);
+
+            for &column in &columns {
+                if let (height, Some(below)) = top[column] {
+                    if height == floor && !supported_by[idx].contains(&below) {
+                        supported_by[idx].push(below);
+                        supports[below].push(idx);
+                    }
+                }
+            }
+
+            let z = floor + 1;
+            settled[idx].start.2 = z;
+            settled[idx].end.2 = z + brick.height() - 1;
+            for &column in &columns {
+                top[column] = (settled[idx].end.2, Some(idx));
+            }
+        }
+
+        let mut stack = Self { bricks: settled, supports, supported_by, dominators: Vec::new(), dominated: Vec::new() };
+        stack.dominate(&order);
+        stack
+    }
+
+    /// Builds the dominator tree of the support graph, rooted in the ground.
+    ///
+    /// Since the support graph is acyclic and `order` visits every brick after all bricks below
+    /// it, the immediate dominator of a brick is simply the lowest common ancestor of its
+    /// supporters in the tree so far. Ancestors are found by binary lifting, so building the tree
+    /// takes `O(n log n)`, after which the sizes of all subtrees follow in a single pass.
+    fn dominate(&mut self, order: &[usize]) {
+        let n = self.bricks.len();
+        let ground = n;
+        let levels = (usize::BITS - n.leading_zeros()) as usize + 1;
+        let mut ancestors = vec![vec![ground; levels]; n + 1];
+        let mut depths = vec![0; n + 1];
+
+        let lca = |mut a: usize, mut b: usize, ancestors: &[Vec<usize>], depths: &[usize]| {
+            if depths[a] < depths[b] {
+                std::mem::swap(&mut a, &mut b);
+            }
+            for level in (0..levels).rev() {
+                if depths[a] - depths[b] >= 1 << level {
+                    a = ancestors[a][level];
+                }
+            }
+            if a == b {
+                return a;
+            }
+            for level in (0..levels).rev() {
+                if ancestors[a][level] != ancestors[b][level] {
+                    a = ancestors[a][level];
+                    b = ancestors[b][level];
+                }
+            }
+            ancestors[a][0]
+        };
+
+        for &idx in order {
+            let dominator = self.supported_by[idx].iter()
+                .copied()
+                .reduce(|a, b| lca(a, b, &ancestors, &depths))
+                .unwrap_or(ground);
+
+            depths[idx] = depths[dominator] + 1;
+            ancestors[idx][0] = dominator;
+            for level in 1..levels {
+                ancestors[idx][level] = ancestors[ancestors[idx][level - 1]][level - 1];
+            }
+        }
+
+        let mut dominated = vec![0; n + 1];
+        for &idx in order.iter().rev() {
+            dominated[ancestors[idx][0]] += dominated[idx] + 1;
+        }
+        dominated.truncate(n);
+
+        self.dominators = (0..n).map(|idx| Some(ancestors[idx][0]).filter(|&dominator| dominator != ground)).collect();
+        self.dominated = dominated;
+    }
+
+    /// The bricks in their resting positions.
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// The bricks resting directly on top of a brick.
+    pub fn supports(&self, brick: usize) -> &[usize] {
+        &self.supports[brick]
+    }
+
+    /// The bricks a brick rests on directly. Bricks on the ground are not supported by any.
+    pub fn supported_by(&self, brick: usize) -> &[usize] {
+        &self.supported_by[brick]
+    }
+
+    /// The last brick below a brick whose removal makes it fall, or `None` if only taking away
+    /// the ground would.
+    pub fn dominator(&self, brick: usize) -> Option<usize> {
+        self.dominators[brick]
+    }
+
+    /// Whether a brick can be disintegrated without any other brick falling.
+    pub fn is_safe_to_remove(&self, brick: usize) -> bool {
+        self.supports[brick].iter().all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    /// All bricks that can be disintegrated without any other brick falling.
+    pub fn safe_to_remove(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.bricks.len()).filter(|&brick| self.is_safe_to_remove(brick))
+    }
+
+    /// The number of other bricks that fall when a brick is disintegrated, which are exactly the
+    /// bricks it dominates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day22::parse;
+    ///
+    /// let stack = parse("1,0,1~1,2,1
+    /// 0,0,2~2,0,2
+    /// 0,2,3~2,2,3
+    /// 0,0,4~0,2,4
+    /// 2,0,5~2,2,5
+    /// 0,1,6~2,1,6
+    /// 1,1,8~1,1,9").unwrap();
+    ///
+    /// assert_eq!(6, stack.falling(0));
+    /// assert_eq!(0, stack.falling(1));
+    /// assert_eq!(1, stack.falling(5));
+    /// assert_eq!(vec![1, 2, 3, 4, 6], stack.safe_to_remove().collect::<Vec<_>>());
+    /// assert_eq!(Some(0), stack.dominator(5));
+    /// assert_eq!(&[1, 2], stack.supported_by(3));
+    /// assert_eq!((1, 1, 5), stack.bricks()[6].start);
+    /// ```
+    pub fn falling(&self, brick: usize) -> usize {
+        self.dominated[brick]
+    }
+}
+
+fn coordinate(input: &str) -> IResult<&str, Coordinate> {
+    map(tuple((terminated(u32, char(',')), terminated(u32, char(',')), u32)), |(x, y, z)| (x, y, z))(input)
+}
+
+fn bricks(input: &str) -> IResult<&str, Vec<(Coordinate, Coordinate)>> {
+    terminated(separated_list0(line_ending, separated_pair(coordinate, char('~'), coordinate)), multispace0)(input)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day22::{ parse, Day22Error };
+///
+/// assert!(matches!(parse("0,0,1~1,1,1"), Err(Day22Error::NotStraight(0))));
+/// assert!(matches!(parse("0,0,1~0,0,2\n0,0,0~0,1,0"), Err(Day22Error::Underground(1))));
+/// ```
+#[aoc_generator(day22)]
+pub fn parse(input: &str) -> Result<Stack, Day22Error> {
+    let raw = match bricks(input) {
+        Ok(("", raw)) => raw,
+        Ok((rest, _)) => return Err(Day22Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day22Error::ParseError(err.to_owned())),
+    };
+
+    let bricks = raw.into_iter().enumerate().map(|(idx, (a, b))| {
+        let start = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        let end = (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2));
+
+        let extents = [end.0 - start.0, end.1 - start.1, end.2 - start.2];
+        if extents.iter().filter(|&&extent| extent > 0).count() > 1 {
+            return Err(Day22Error::NotStraight(idx));
+        }
+        if start.2 == 0 {
+            return Err(Day22Error::Underground(idx));
+        }
+
+        Ok(Brick { start, end })
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Stack::settle(&bricks))
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day22::{ parse, part1 };
+/// assert_eq!(5, part1(&parse("1,0,1~1,2,1
+/// 0,0,2~2,0,2
+/// 0,2,3~2,2,3
+/// 0,0,4~0,2,4
+/// 2,0,5~2,2,5
+/// 0,1,6~2,1,6
+/// 1,1,8~1,1,9").unwrap()));
+/// ```
+#[aoc(day22, part1)]
+pub fn part1(stack: &Stack) -> usize {
+    stack.safe_to_remove().count()
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day22::{ parse, part2 };
+/// assert_eq!(7, part2(&parse("1,0,1~1,2,1
+/// 0,0,2~2,0,2
+/// 0,2,3~2,2,3
+/// 0,0,4~0,2,4
+/// 2,0,5~2,2,5
+/// 0,1,6~2,1,6
+/// 1,1,8~1,1,9").unwrap()));
+/// ```
+#[aoc(day22, part2)]
+pub fn part2(stack: &Stack) -> usize {
+    (0..stack.bricks().len()).map(|brick| stack.falling(brick)).sum()
+}
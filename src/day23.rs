@@ -0,0 +1,327 @@
+use std::fmt::Write;
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use thiserror::Error;
+
+use crate::grid::{ Grid, GridError };
+
+type Coordinate = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    tiles: Vec<u8>,
+    width: usize,
+    height: usize,
+    start: Coordinate,
+    end: Coordinate,
+}
+
+/// The trails compressed into the junctions where they fork, connected by the corridors between
+/// them, weighted by the number of steps along each corridor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    junctions: Vec<Coordinate>,
+    edges: Vec<Vec<(usize, u32)>>,
+    start: usize,
+    end: usize,
+    directed: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum Day23Error {
+    #[error(transparent)]
+    Grid(#[from] GridError),
+    #[error("The first row has to have exactly one path")]
+    MissingStart,
+    #[error("The last row has to have exactly one path")]
+    MissingEnd,
+    #[error("There are {0} junctions, but at most 64 are supported")]
+    TooManyJunctions(usize),
+    #[error("There is no hike from the start to the end")]
+    NoHike,
+}
+
+const FOREST: u8 = b'#';
+const PATH: u8 = b'.';
+/// The slopes, in the order of [`STEPS`].
+const SLOPES: [u8; 4] = [b'^', b'>', b'v', b'<'];
+const STEPS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+impl Map {
+    fn tile(&self, (x, y): Coordinate) -> u8 {
+        self.tiles[y * self.width + x]
+    }
+
+    /// The tiles that can be stepped on from `position`, with the direction of the step. When
+    /// slopes are `slippery`, a slope can only be left downhill and never be climbed.
+    fn steps(&self, position: Coordinate, slippery: bool) -> impl Iterator<Item = (Coordinate, usize)> + '_ {
+        let here = self.tile(position);
+
+        STEPS.iter().enumerate().filter_map(move |(direction, &(dx, dy))| {
+            let x = position.0.checked_add_signed(dx).filter(|&x| x < self.width)?;
+            let y = position.1.checked_add_signed(dy).filter(|&y| y < self.height)?;
+            let there = self.tile((x, y));
+
+            let allowed = there != FOREST && (!slippery || (
+                (here == PATH || here == SLOPES[direction]) &&
+                (there == PATH || there != SLOPES[(direction + 2) % 4])
+            ));
+            allowed.then_some(((x, y), direction))
+        })
+    }
+
+    fn is_junction(&self, position: Coordinate) -> bool {
+        position == self.start || position == self.end || self.steps(position, false).count() > 2
+    }
+
+    /// Compresses the trails into a graph of junctions. With `slippery` slopes, the corridors
+    /// become one way and the graph is directed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day23::parse;
+    ///
+    /// let map = parse(&[
+    ///     "#.###",
+    ///     "#...#",
+    ///     "#.#.#",
+    ///     "#...#",
+    ///     "###.#",
+    /// ].join("\n")).unwrap();
+    ///
+    /// let graph = map.compress(false).unwrap();
+    /// assert_eq!(&[(1, 0), (1, 1), (3, 3), (3, 4)], graph.junctions());
+    /// assert_eq!(&[(1, 1)], graph.edges(0));
+    /// assert_eq!(&[(0, 1), (2, 4)], graph.edges(1));
+    /// ```
+    pub fn compress(&self, slippery: bool) -> Result<Graph, Day23Error> {
+        let junctions: Vec<Coordinate> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&position| self.tile(position) != FOREST && self.is_junction(position))
+            .collect();
+
+        if junctions.len() > u64::BITS as usize {
+            return Err(Day23Error::TooManyJunctions(junctions.len()));
+        }
+
+        let index = |position: Coordinate| junctions.iter().position(|&junction| junction == position);
+        let mut edges = vec![Vec::new(); junctions.len()];
+
+        for (from, &junction) in junctions.iter().enumerate() {
+            for (first, _) in self.steps(junction, slippery) {
+                let Some((to, length)) = self.follow(junction, first, slippery) else { continue };
+                let Some(to) = index(to).filter(|&to| to != from) else { continue };
+
+                // Two corridors may connect the same junctions, only the longer one matters.
+                match edges[from].iter_mut().find(|(existing, _)| *existing == to) {
+                    Some((_, existing)) => *existing = length.max(*existing),
+                    None => edges[from].push((to, length)),
+                }
+            }
+        }
+
+        Ok(Graph {
+            start: index(self.start).ok_or(Day23Error::MissingStart)?,
+            end: index(self.end).ok_or(Day23Error::MissingEnd)?,
+            junctions,
+            edges,
+            directed: slippery,
+        })
+    }
+
+    /// Walks down the corridor from `junction` through `next` up to the next junction, and
+    /// returns it together with the length of the corridor. Returns `None` for dead ends.
+    fn follow(&self, junction: Coordinate, mut next: Coordinate, slippery: bool) -> Option<(Coordinate, u32)> {
+        let mut previous = junction;
+        let mut length = 1;
+
+        while !self.is_junction(next) {
+            let (step, _) = self.steps(next, slippery).find(|&(step, _)| step != previous)?;
+            (previous, next) = (next, step);
+            length += 1;
+        }
+
+        Some((next, length))
+    }
+}
+
+impl Graph {
+    pub fn junctions(&self) -> &[Coordinate] {
+        &self.junctions
+    }
+
+    /// The junctions reachable from a junction with a single corridor, with the length of the
+    /// corridor.
+    pub fn edges(&self, junction: usize) -> &[(usize, u32)] {
+        &self.edges[junction]
+    }
+
+    /// The length of the longest hike from the start to the end that never visits a tile twice.
+    ///
+    /// This is a depth first search over the junctions, remembering the visited ones in a bit
+    /// mask. The end usually has a single neighbour, and once the hike reaches that junction it
+    /// has to head for the end, as any other way would block the only way there.
+    pub fn longest_hike(&self) -> Option<u32> {
+        let predecessors: Vec<usize> = (0..self.junctions.len())
+            .filter(|&junction| self.edges[junction].iter().any(|&(to, _)| to == self.end))
+            .collect();
+        let last = match predecessors[..] {
+            [last] => Some(last),
+            _ => None,
+        };
+
+        self.hike(self.start, 1 << self.start, last)
+    }
+
+    fn hike(&self, junction: usize, visited: u64, last: Option<usize>) -> Option<u32> {
+        if junction == self.end {
+            return Some(0);
+        }
+
+        self.edges[junction].iter()
+            .filter(|&&(to, _)| visited & (1 << to) == 0)
+            .filter(|&&(to, _)| last != Some(junction) || to == self.end)
+            .filter_map(|&(to, length)| Some(length + self.hike(to, visited | 1 << to, last)?))
+            .max()
+    }
+
+    /// Writes the graph in Graphviz format, labelling the junctions with their coordinates and
+    /// the corridors with their lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day23::parse;
+    ///
+    /// let map = parse(&[
+    ///     "#.###",
+    ///     "#.>.#",
+    ///     "#v#v#",
+    ///     "#...#",
+    ///     "###.#",
+    /// ].join("\n")).unwrap();
+    ///
+    /// assert_eq!(r#"digraph trails {
+    ///     j0 [label="(1, 0)"];
+    ///     j1 [label="(1, 1)"];
+    ///     j2 [label="(3, 3)"];
+    ///     j3 [label="(3, 4)"];
+    ///     j0 -> j1 [label="1"];
+    ///     j1 -> j0 [label="1"];
+    ///     j1 -> j2 [label="4"];
+    ///     j2 -> j3 [label="1"];
+    ///     j3 -> j2 [label="1"];
+    /// }
+    /// "#, map.compress(true).unwrap().to_dot());
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        let mut dot = format!("{kind} trails {{\n");
+
+        for (idx, junction) in self.junctions.iter().enumerate() {
+            let _ = writeln!(dot, "    j{idx} [label=\"{junction:?}\"];");
+        }
+
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, length) in edges {
+                if self.directed || from < to {
+                    let _ = writeln!(dot, "    j{from} {arrow} j{to} [label=\"{length}\"];");
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[aoc_generator(day23)]
+pub fn parse(input: &str) -> Result<Map, Day23Error> {
+    let Grid { tiles, width, height } = Grid::parse(input, |chr| {
+        u8::try_from(chr).ok().filter(|tile| matches!(*tile, FOREST | PATH | b'^' | b'>' | b'v' | b'<'))
+    })?;
+
+    let only_path = |row: &[u8]| {
+        let mut paths = row.iter().enumerate().filter(|&(_, &tile)| tile == PATH);
+        match (paths.next(), paths.next()) {
+            (Some((x, _)), None) => Some(x),
+            _ => None,
+        }
+    };
+
+    let start = tiles.chunks(width.max(1)).next().and_then(only_path).ok_or(Day23Error::MissingStart)?;
+    let end = tiles.chunks(width.max(1)).last().and_then(only_path).ok_or(Day23Error::MissingEnd)?;
+
+    Ok(Map { tiles, width, height, start: (start, 0), end: (end, height - 1) })
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day23::{ parse, part1 };
+/// assert_eq!(94, part1(&parse(&[
+///     "#.#####################",
+///     "#.......#########...###",
+///     "#######.#########.#.###",
+///     "###.....#.>.>.###.#.###",
+///     "###v#####.#v#.###.#.###",
+///     "###.>...#.#.#.....#...#",
+///     "###v###.#.#.#########.#",
+///     "###...#.#.#.......#...#",
+///     "#####.#.#.#######.#.###",
+///     "#.....#.#.#.......#...#",
+///     "#.#####.#.#.#########v#",
+///     "#.#...#...#...###...>.#",
+///     "#.#.#v#######v###.###v#",
+///     "#...#.>.#...>.>.#.###.#",
+///     "#####v#.#.###v#.#.###.#",
+///     "#.....#...#...#.#.#...#",
+///     "#.#########.###.#.#.###",
+///     "#...###...#...#...#.###",
+///     "###.###.#.###v#####v###",
+///     "#...#...#.#.>.>.#.>.###",
+///     "#.###.###.#.###.#.#v###",
+///     "#.....###...###...#...#",
+///     "#####################.#",
+/// ].join("\n")).unwrap()).unwrap());
+/// ```
+#[aoc(day23, part1)]
+pub fn part1(map: &Map) -> Result<u32, Day23Error> {
+    map.compress(true)?.longest_hike().ok_or(Day23Error::NoHike)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day23::{ parse, part2 };
+/// assert_eq!(154, part2(&parse(&[
+///     "#.#####################",
+///     "#.......#########...###",
+///     "#######.#########.#.###",
+///     "###.....#.>.>.###.#.###",
+///     "###v#####.#v#.###.#.###",
+///     "###.>...#.#.#.....#...#",
+///     "###v###.#.#.#########.#",
+///     "###...#.#.#.......#...#",
+///     "#####.#.#.#######.#.###",
+///     "#.....#.#.#.......#...#",
+///     "#.#####.#.#.#########v#",
+///     "#.#...#...#...###...>.#",
+///     "#.#.#v#######v###.###v#",
+///     "#...#.>.#...>.>.#.###.#",
+///     "#####v#.#.###v#.#.###.#",
+///     "#.....#...#...#.#.#...#",
+///     "#.#########.###.#.#.###",
+///     "#...###...#...#...#.###",
+///     "###.###.#.###v#####v###",
+///     "#...#...#.#.>.>.#.>.###",
+///     "#.###.###.#.###.#.#v###",
+///     "#.....###...###...#...#",
+///     "#####################.#",
+/// ].join("\n")).unwrap()).unwrap());
+/// ```
+#[aoc(day23, part2)]
+pub fn part2(map: &Map) -> Result<u32, Day23Error> {
+    map.compress(false)?.longest_hike().ok_or(Day23Error::NoHike)
+}
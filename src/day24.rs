@@ -0,0 +1,330 @@
+use std::ops::RangeInclusive;
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ bytes::complete::tag, character::complete::{ char, i64, line_ending, multispace0, space0, space1 }, combinator::map, multi::separated_list0, sequence::{ separated_pair, terminated, tuple }, Err, error::Error, IResult };
+use num::{ BigInt, BigRational, Zero };
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hailstone {
+    pub position: [i64; 3],
+    pub velocity: [i64; 3],
+}
+
+/// The point where the paths of two hailstones cross in the x/y plane, as exact fractions
+/// `x / denominator` and `y / denominator` with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crossing {
+    pub x: i128,
+    pub y: i128,
+    pub denominator: i128,
+}
+
+/// A throw of the rock, in exact fractions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Throw {
+    pub position: [BigRational; 3],
+    pub velocity: [BigRational; 3],
+}
+
+#[derive(Debug, Error)]
+pub enum Day24Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("The hailstones do not determine a single throw")]
+    Underdetermined,
+    #[error("The hailstones do not all lie on the path of a single throw")]
+    Inconsistent,
+    #[error("The throw misses hailstone {0}")]
+    Missed(usize),
+    #[error("The rock does not start at a whole number position")]
+    Fractional,
+}
+
+/// The area in which crossings count in part 1, on both axes.
+pub const TEST_AREA: RangeInclusive<i64> = 200_000_000_000_000..=400_000_000_000_000;
+
+impl Crossing {
+    /// Whether the crossing lies inside `area` on both axes.
+    pub fn within(&self, area: &RangeInclusive<i64>) -> bool {
+        let (min, max) = (*area.start() as i128 * self.denominator, *area.end() as i128 * self.denominator);
+
+        (min..=max).contains(&self.x) && (min..=max).contains(&self.y)
+    }
+
+    pub fn point(&self) -> (BigRational, BigRational) {
+        let denominator = BigInt::from(self.denominator);
+
+        (
+            BigRational::new(self.x.into(), denominator.clone()),
+            BigRational::new(self.y.into(), denominator),
+        )
+    }
+}
+
+impl Hailstone {
+    /// Where the paths of two hailstones cross in the x/y plane, ignoring the z axis, if they
+    /// cross at a time that is not in the past for either hailstone.
+    ///
+    /// Solving `p1 + t v1 = p2 + s v2` with cross products gives `t` and `s` as fractions over the
+    /// same denominator, so everything is done in `i128` without rounding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day24::{ parse, Crossing };
+    /// use num::BigRational;
+    ///
+    /// let hailstones = parse("19, 13, 30 @ -2, 1, -2
+    /// 18, 19, 22 @ -1, -1, -2
+    /// 20, 19, 15 @  1, -5, -3").unwrap();
+    ///
+    /// let crossing = hailstones[0].crossing(&hailstones[1]).unwrap();
+    /// assert_eq!((BigRational::new(43.into(), 3.into()), BigRational::new(46.into(), 3.into())), crossing.point());
+    /// assert!(crossing.within(&(7..=27)));
+    ///
+    /// assert_eq!(None, hailstones[0].crossing(&hailstones[2]));
+    /// ```
+    pub fn crossing(&self, other: &Hailstone) -> Option<Crossing> {
+        let [px1, py1, _] = self.position.map(i128::from);
+        let [vx1, vy1, _] = self.velocity.map(i128::from);
+        let [px2, py2, _] = other.position.map(i128::from);
+        let [vx2, vy2, _] = other.velocity.map(i128::from);
+
+        let mut denominator = vx1 * vy2 - vy1 * vx2;
+        if denominator == 0 {
+            return None;
+        }
+
+        let (dx, dy) = (px2 - px1, py2 - py1);
+        let (mut t, mut s) = (dx * vy2 - dy * vx2, dx * vy1 - dy * vx1);
+        if denominator < 0 {
+            (denominator, t, s) = (-denominator, -t, -s);
+        }
+        if t < 0 || s < 0 {
+            return None;
+        }
+
+        Some(Crossing { x: px1 * denominator + t * vx1, y: py1 * denominator + t * vy1, denominator })
+    }
+}
+
+/// Counts the pairs of hailstones whose future paths cross inside `area`.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day24::{ parse, crossings_within };
+/// assert_eq!(2, crossings_within(&parse("19, 13, 30 @ -2,  1, -2
+/// 18, 19, 22 @ -1, -1, -2
+/// 20, 25, 34 @ -2, -2, -4
+/// 12, 31, 28 @ -1, -2, -1
+/// 20, 19, 15 @  1, -5, -3").unwrap(), 7..=27));
+/// ```
+pub fn crossings_within(hailstones: &[Hailstone], area: RangeInclusive<i64>) -> usize {
+    hailstones.iter().enumerate().map(|(idx, first)| {
+        hailstones[idx + 1..].iter()
+            .filter_map(|second| first.crossing(second))
+            .filter(|crossing| crossing.within(&area))
+            .count()
+    }).sum()
+}
+
+/// The cross product of two vectors.
+fn cross<T: Clone + std::ops::Mul<Output = T> + std::ops::Sub<Output = T>>(a: &[T; 3], b: &[T; 3]) -> [T; 3] {
+    let product = |i: usize, j: usize| a[i].clone() * b[j].clone() - a[j].clone() * b[i].clone();
+    [product(1, 2), product(2, 0), product(0, 1)]
+}
+
+/// Solves a system of linear equations by Gauss-Jordan elimination. Every row holds the
+/// coefficients of the unknowns followed by the right hand side. There may be more equations than
+/// unknowns, as long as they agree with each other.
+///
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day24::solve_linear;
+/// use num::BigRational;
+///
+/// let rows = [[1, 1, 3], [1, -1, 1], [2, 2, 6]].map(|row| row.map(|value| BigRational::from_integer(value.into())).to_vec());
+/// assert_eq!(vec![BigRational::from_integer(2.into()), BigRational::from_integer(1.into())], solve_linear(rows.to_vec(), 2).unwrap());
+/// ```
+pub fn solve_linear(mut rows: Vec<Vec<BigRational>>, unknowns: usize) -> Result<Vec<BigRational>, Day24Error> {
+    for column in 0..unknowns {
+        let pivot = (column..rows.len())
+            .find(|&row| !rows[row][column].is_zero())
+            .ok_or(Day24Error::Underdetermined)?;
+        rows.swap(column, pivot);
+
+        let divisor = rows[column][column].clone();
+        let pivot: Vec<BigRational> = rows[column].iter().map(|value| value / &divisor).collect();
+
+        for (idx, row) in rows.iter_mut().enumerate() {
+            if idx == column || row[column].is_zero() {
+                continue;
+            }
+
+            let factor = row[column].clone();
+            for (value, pivot) in row.iter_mut().zip(&pivot) {
+                *value -= &factor * pivot;
+            }
+        }
+        rows[column] = pivot;
+    }
+
+    if rows[unknowns..].iter().any(|row| !row[unknowns].is_zero()) {
+        return Err(Day24Error::Inconsistent);
+    }
+
+    Ok(rows.into_iter().take(unknowns).map(|row| row[unknowns].clone()).collect())
+}
+
+impl Throw {
+    /// Finds the throw that hits every hailstone.
+    ///
+    /// A rock at `P` with velocity `V` hits a hailstone at `p` with velocity `v` exactly when
+    /// `P - p` and `V - v` are parallel, so `(P - p) x (V - v) = 0`. The only term that is not
+    /// linear is `P x V`, which is the same for every hailstone, so subtracting the equations of
+    /// two hailstones gives three linear equations. A few pairs give more than enough equations
+    /// for the six unknowns, and the result is then checked against every hailstone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day24::{ parse, Throw, Day24Error };
+    /// use num::BigRational;
+    ///
+    /// let hailstones = parse("19, 13, 30 @ -2,  1, -2
+    /// 18, 19, 22 @ -1, -1, -2
+    /// 20, 25, 34 @ -2, -2, -4
+    /// 12, 31, 28 @ -1, -2, -1
+    /// 20, 19, 15 @  1, -5, -3").unwrap();
+    ///
+    /// let throw = Throw::hitting(&hailstones).unwrap();
+    /// assert_eq!([24, 13, 10].map(|value| BigRational::from_integer(value.into())), throw.position);
+    /// assert_eq!([-3, 1, 2].map(|value| BigRational::from_integer(value.into())), throw.velocity);
+    ///
+    /// let mut missed = hailstones.clone();
+    /// missed[4].position[2] += 1;
+    /// assert!(matches!(Throw::hitting(&missed), Err(Day24Error::Inconsistent | Day24Error::Missed(_))));
+    /// ```
+    pub fn hitting(hailstones: &[Hailstone]) -> Result<Self, Day24Error> {
+        let rational = |values: [i64; 3]| values.map(|value| BigRational::from_integer(value.into()));
+        let first = hailstones.first().ok_or(Day24Error::Underdetermined)?;
+        let (p0, v0) = (rational(first.position), rational(first.velocity));
+
+        let mut rows = Vec::new();
+        for other in hailstones.iter().skip(1).take(4) {
+            let (p, v) = (rational(other.position), rational(other.velocity));
+            let w: [BigRational; 3] = std::array::from_fn(|i| &v[i] - &v0[i]);
+            let d: [BigRational; 3] = std::array::from_fn(|i| &p[i] - &p0[i]);
+            let (moment, moment0) = (cross(&p, &v), cross(&p0, &v0));
+            let rhs: [BigRational; 3] = std::array::from_fn(|i| &moment[i] - &moment0[i]);
+            let zero = BigRational::zero;
+
+            // P x w + d x V = p x v - p0 x v0, written out per axis for [Px, Py, Pz, Vx, Vy, Vz].
+            rows.push(vec![zero(), w[2].clone(), -&w[1], zero(), -&d[2], d[1].clone(), rhs[0].clone()]);
+            rows.push(vec![-&w[2], zero(), w[0].clone(), d[2].clone(), zero(), -&d[0], rhs[1].clone()]);
+            rows.push(vec![w[1].clone(), -&w[0], zero(), -&d[1], d[0].clone(), zero(), rhs[2].clone()]);
+        }
+
+        let solution = solve_linear(rows, 6)?;
+        let throw = Throw {
+            position: std::array::from_fn(|i| solution[i].clone()),
+            velocity: std::array::from_fn(|i| solution[i + 3].clone()),
+        };
+
+        match hailstones.iter().position(|hailstone| throw.hit_time(hailstone).is_none()) {
+            Some(missed) => Err(Day24Error::Missed(missed)),
+            None => Ok(throw),
+        }
+    }
+
+    /// The time at which the rock hits a hailstone, if it does so at some point that is not in the
+    /// past. A hailstone that moves along with the rock is hit right away.
+    pub fn hit_time(&self, hailstone: &Hailstone) -> Option<BigRational> {
+        let mut time: Option<BigRational> = None;
+
+        for axis in 0..3 {
+            let distance = BigRational::from_integer(hailstone.position[axis].into()) - &self.position[axis];
+            let closing = &self.velocity[axis] - BigRational::from_integer(hailstone.velocity[axis].into());
+
+            if closing.is_zero() {
+                if !distance.is_zero() {
+                    return None;
+                }
+                continue;
+            }
+
+            let t = distance / closing;
+            if time.as_ref().is_some_and(|time| *time != t) {
+                return None;
+            }
+            time = Some(t);
+        }
+
+        Some(time.unwrap_or_else(BigRational::zero)).filter(|time| *time >= BigRational::zero())
+    }
+}
+
+fn triple(input: &str) -> IResult<&str, [i64; 3]> {
+    map(
+        tuple((
+            terminated(i64, tuple((char(','), space0))),
+            terminated(i64, tuple((char(','), space0))),
+            i64,
+        )),
+        |(x, y, z)| [x, y, z],
+    )(input)
+}
+
+fn hailstones(input: &str) -> IResult<&str, Vec<Hailstone>> {
+    terminated(
+        separated_list0(
+            line_ending,
+            map(
+                separated_pair(triple, tuple((space1, tag("@"), space1)), triple),
+                |(position, velocity)| Hailstone { position, velocity },
+            ),
+        ),
+        multispace0,
+    )(input)
+}
+
+#[aoc_generator(day24)]
+pub fn parse(input: &str) -> Result<Vec<Hailstone>, Day24Error> {
+    match hailstones(input) {
+        Ok(("", hailstones)) => Ok(hailstones),
+        Ok((rest, _)) => Err(Day24Error::Rest(rest.to_owned())),
+        Err(err) => Err(Day24Error::ParseError(err.to_owned())),
+    }
+}
+
+#[aoc(day24, part1)]
+pub fn part1(hailstones: &[Hailstone]) -> usize {
+    crossings_within(hailstones, TEST_AREA)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day24::{ parse, part2 };
+/// assert_eq!(num::BigInt::from(47), part2(&parse("19, 13, 30 @ -2,  1, -2
+/// 18, 19, 22 @ -1, -1, -2
+/// 20, 25, 34 @ -2, -2, -4
+/// 12, 31, 28 @ -1, -2, -1
+/// 20, 19, 15 @  1, -5, -3").unwrap()).unwrap());
+/// ```
+#[aoc(day24, part2)]
+pub fn part2(hailstones: &[Hailstone]) -> Result<BigInt, Day24Error> {
+    let throw = Throw::hitting(hailstones)?;
+    let sum: BigRational = throw.position.iter().sum();
+
+    if sum.is_integer() {
+        Ok(sum.to_integer())
+    } else {
+        Err(Day24Error::Fractional)
+    }
+}
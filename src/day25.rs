@@ -0,0 +1,254 @@
+use std::collections::{ BTreeMap, BinaryHeap, HashMap };
+
+use aoc_runner_derive::{ aoc, aoc_generator };
+use nom::{ bytes::complete::tag, character::complete::{ alpha1, line_ending, multispace0, space1 }, multi::{ separated_list0, separated_list1 }, sequence::{ separated_pair, terminated }, Err, error::Error, IResult };
+use thiserror::Error;
+
+/// The components and the wires between them. Every wire is listed at both of its ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    names: Vec<String>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+/// A way to split the components in two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    /// The components on one side of the cut, in ascending order.
+    pub side: Vec<usize>,
+    /// The wires between the two sides, with the lower component first.
+    pub edges: Vec<(usize, usize)>,
+    /// The number of components on either side.
+    pub sizes: (usize, usize),
+}
+
+#[derive(Debug, Error)]
+pub enum Day25Error {
+    #[error("Failed to parse due to remainder: {0}")]
+    Rest(String),
+    #[error("Failed to parse due to nom error: {0}")]
+    ParseError(Err<Error<String>>),
+    #[error("There have to be at least two components to split them")]
+    TooSmall,
+    #[error("The smallest cut has {0} wires instead of three")]
+    UnexpectedCut(usize),
+}
+
+/// The number of wires that have to be disconnected.
+pub const WIRES: usize = 3;
+
+impl Cut {
+    /// The product of the sizes of both sides.
+    pub fn product(&self) -> usize {
+        self.sizes.0 * self.sizes.1
+    }
+}
+
+impl Graph {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, component: usize) -> &str {
+        &self.names[component]
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|existing| existing == name)
+    }
+
+    pub fn neighbours(&self, component: usize) -> &[usize] {
+        &self.neighbours[component]
+    }
+
+    /// Finds a cut through the fewest wires with the Stoer-Wagner algorithm.
+    ///
+    /// Every phase orders the remaining vertices by how strongly they are connected to the ones
+    /// before them, using a priority queue. The last vertex in that order is separated from the
+    /// rest by a cut whose weight is its connectivity, and is then merged into the one before it.
+    /// The lightest of these cuts is a minimum cut. Ties are broken by the lowest index, so the
+    /// result does not depend on anything but the graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::day25::parse;
+    ///
+    /// let graph = parse("a: b c d
+    /// b: c d
+    /// c: d
+    /// d: e
+    /// e: f g h
+    /// f: g h
+    /// g: h").unwrap();
+    ///
+    /// let cut = graph.min_cut().unwrap();
+    /// assert_eq!(vec![(graph.index("d").unwrap(), graph.index("e").unwrap())], cut.edges);
+    /// assert_eq!((4, 4), cut.sizes);
+    /// ```
+    pub fn min_cut(&self) -> Option<Cut> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mut weights: Vec<BTreeMap<usize, u32>> = self.neighbours.iter()
+            .map(|neighbours| neighbours.iter().map(|&neighbour| (neighbour, 1)).collect())
+            .collect();
+        let mut members: Vec<Vec<usize>> = (0..n).map(|vertex| vec![vertex]).collect();
+        let mut active: Vec<usize> = (0..n).collect();
+        let mut best: Option<(u32, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let (s, t, cut) = Self::phase(&weights, &active, n);
+
+            if best.as_ref().is_none_or(|(weight, _)| cut < *weight) {
+                best = Some((cut, members[t].clone()));
+            }
+
+            let merged = std::mem::take(&mut weights[t]);
+            for (vertex, weight) in merged {
+                weights[vertex].remove(&t);
+                if vertex != s {
+                    *weights[s].entry(vertex).or_insert(0) += weight;
+                    *weights[vertex].entry(s).or_insert(0) += weight;
+                }
+            }
+
+            let moved = std::mem::take(&mut members[t]);
+            members[s].extend(moved);
+            active.retain(|&vertex| vertex != t);
+        }
+
+        let (_, mut side) = best?;
+        side.sort_unstable();
+
+        let mut inside = vec![false; n];
+        for &vertex in &side {
+            inside[vertex] = true;
+        }
+
+        let edges = (0..n)
+            .flat_map(|from| self.neighbours[from].iter().map(move |&to| (from, to)))
+            .filter(|&(from, to)| from < to && inside[from] != inside[to])
+            .collect();
+
+        Some(Cut { sizes: (side.len(), n - side.len()), side, edges })
+    }
+
+    /// Runs a single maximum adjacency ordering over the active vertices, and returns the last
+    /// two vertices together with the weight of the cut that separates the last one.
+    fn phase(weights: &[BTreeMap<usize, u32>], active: &[usize], n: usize) -> (usize, usize, u32) {
+        let mut connectivity = vec![0u32; n];
+        let mut added = vec![false; n];
+        let mut queue: BinaryHeap<(u32, std::cmp::Reverse<usize>)> = BinaryHeap::new();
+
+        let (mut previous, mut last, mut cut) = (active[0], active[0], 0);
+        let mut remaining = active.len();
+
+        while remaining > 0 {
+            let (weight, vertex) = match queue.pop() {
+                Some((weight, std::cmp::Reverse(vertex))) => (weight, vertex),
+                // A disconnected graph leaves vertices behind that nothing points to.
+                None => (0, *active.iter().find(|&&vertex| !added[vertex]).expect("a vertex is left")),
+            };
+            if added[vertex] || weight != connectivity[vertex] {
+                continue;
+            }
+
+            added[vertex] = true;
+            (previous, last, cut) = (last, vertex, weight);
+            remaining -= 1;
+
+            for (&neighbour, &weight) in &weights[vertex] {
+                if !added[neighbour] {
+                    connectivity[neighbour] += weight;
+                    queue.push((connectivity[neighbour], std::cmp::Reverse(neighbour)));
+                }
+            }
+        }
+
+        (previous, last, cut)
+    }
+}
+
+fn lines(input: &str) -> IResult<&str, Vec<(&str, Vec<&str>)>> {
+    terminated(
+        separated_list0(line_ending, separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1))),
+        multispace0,
+    )(input)
+}
+
+#[aoc_generator(day25)]
+pub fn parse(input: &str) -> Result<Graph, Day25Error> {
+    let lines = match lines(input) {
+        Ok(("", lines)) => lines,
+        Ok((rest, _)) => return Err(Day25Error::Rest(rest.to_owned())),
+        Err(err) => return Err(Day25Error::ParseError(err.to_owned())),
+    };
+
+    let mut indices = HashMap::new();
+    let mut graph = Graph { names: Vec::new(), neighbours: Vec::new() };
+    let mut index = |graph: &mut Graph, name: &str| *indices.entry(name.to_owned()).or_insert_with(|| {
+        graph.names.push(name.to_owned());
+        graph.neighbours.push(Vec::new());
+        graph.names.len() - 1
+    });
+
+    for (component, connected) in lines {
+        let from = index(&mut graph, component);
+        for other in connected {
+            let to = index(&mut graph, other);
+            if from != to && !graph.neighbours[from].contains(&to) {
+                graph.neighbours[from].push(to);
+                graph.neighbours[to].push(from);
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// # Examples
+///
+/// ```
+/// use aoc_2023::day25::{ parse, part1 };
+///
+/// let graph = parse("jqt: rhn xhk nvd
+/// rsh: frs pzl lsr
+/// xhk: hfx
+/// cmg: qnr nvd lhk bvb
+/// rhn: xhk bvb hfx
+/// bvb: xhk hfx
+/// pzl: lsr hfx nvd
+/// qnr: nvd
+/// ntq: jqt hfx bvb xhk
+/// nvd: lhk
+/// lsr: lhk
+/// rzs: qnr cmg lsr rsh
+/// frs: qnr lhk lsr").unwrap();
+///
+/// assert_eq!(54, part1(&graph).unwrap());
+///
+/// let mut cut: Vec<_> = graph.min_cut().unwrap().edges.into_iter().map(|(from, to)| {
+///     let mut names = [graph.name(from), graph.name(to)];
+///     names.sort();
+///     names
+/// }).collect();
+/// cut.sort();
+/// assert_eq!(vec![["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]], cut);
+/// ```
+#[aoc(day25, part1)]
+pub fn part1(graph: &Graph) -> Result<usize, Day25Error> {
+    let cut = graph.min_cut().ok_or(Day25Error::TooSmall)?;
+
+    if cut.edges.len() != WIRES {
+        return Err(Day25Error::UnexpectedCut(cut.edges.len()));
+    }
+
+    Ok(cut.product())
+}
@@ -0,0 +1,63 @@
+use thiserror::Error;
+
+type Coordinate = (usize, usize);
+
+/// A rectangular grid of tiles, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    pub tiles: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+}
+
+#[derive(Debug, Error)]
+pub enum GridError {
+    #[error("Unknown tile {0:?} at {1:?}")]
+    UnknownTile(char, Coordinate),
+    #[error("Row {0} has a different width than the first row")]
+    Ragged(usize),
+}
+
+impl<T> Grid<T> {
+    /// Parses a grid with one character per tile, where `tile` turns a character into a tile, or
+    /// returns `None` if it does not stand for any.
+    ///
+    /// Every row has to have as many characters as the first one. An empty input gives an empty
+    /// grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aoc_2023::grid::{ Grid, GridError };
+    ///
+    /// let grid = Grid::parse("12\n34", |chr| chr.to_digit(10)).unwrap();
+    /// assert_eq!(vec![1, 2, 3, 4], grid.tiles);
+    /// assert_eq!((2, 2), (grid.width, grid.height));
+    ///
+    /// assert!(matches!(Grid::parse("12\n3", |chr| chr.to_digit(10)), Err(GridError::Ragged(1))));
+    /// assert!(matches!(Grid::parse("12\n3x", |chr| chr.to_digit(10)), Err(GridError::UnknownTile('x', (1, 1)))));
+    ///
+    /// let grid = Grid::parse("éa\nbc", Some).unwrap();
+    /// assert_eq!(vec!['é', 'a', 'b', 'c'], grid.tiles);
+    /// assert_eq!((2, 2), (grid.width, grid.height));
+    /// ```
+    pub fn parse(input: &str, tile: impl Fn(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut tiles = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let len = line.chars().count();
+            if *width.get_or_insert(len) != len {
+                return Err(GridError::Ragged(y));
+            }
+
+            for (x, chr) in line.chars().enumerate() {
+                tiles.push(tile(chr).ok_or(GridError::UnknownTile(chr, (x, y)))?);
+            }
+            height += 1;
+        }
+
+        Ok(Self { tiles, width: width.unwrap_or(0), height })
+    }
+}
@@ -22,5 +22,9 @@ pub mod day21;
 pub mod day22;
 pub mod day23;
 pub mod day24;
+pub mod day25;
+
+pub mod cycle;
+pub mod grid;
 
 aoc_runner_derive::aoc_lib!{ year = 2023 }